@@ -0,0 +1,32 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use xlsxwriter::*;
+
+const ROWS: u32 = 100_000;
+const COLS: u16 = 10;
+const LABELS: [&str; 4] = ["North", "South", "East", "West"];
+
+/// Write 1M short, repeated strings such as category labels.
+fn write_string(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write_string");
+    group.sample_size(10);
+    group.bench_function("1M cells", |b| {
+        b.iter(|| {
+            let workbook =
+                Workbook::new_with_options("../target/bench-write_string.xlsx", true, None, false)
+                    .unwrap();
+            let mut worksheet = workbook.add_worksheet(None).unwrap();
+            for row in 0..ROWS {
+                for col in 0..COLS {
+                    let label = LABELS[(row as usize + col as usize) % LABELS.len()];
+                    worksheet.write_string(row, col, label, None).unwrap();
+                }
+            }
+            // Dropping the workbook without closing it would still write the file, close explicitly to keep it in the measurement.
+            workbook.close().unwrap();
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, write_string);
+criterion_main!(benches);
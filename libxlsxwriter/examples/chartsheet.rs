@@ -0,0 +1,24 @@
+use xlsxwriter::{ChartType, Workbook};
+
+fn main() -> Result<(), xlsxwriter::XlsxError> {
+    let workbook = Workbook::new("target/chartsheet.xlsx");
+    let mut sheet1 = workbook.add_worksheet(None)?;
+    let mut chartsheet = workbook.add_chartsheet(Some("Chart"))?;
+
+    sheet1.write_string(0, 0, "Quarter", None)?;
+    sheet1.write_string(0, 1, "Sales", None)?;
+    for (i, sales) in [120., 160., 90., 200.].iter().enumerate() {
+        let row = i as u32 + 1;
+        sheet1.write_string(row, 0, &format!("Q{}", row), None)?;
+        sheet1.write_number(row, 1, *sales, None)?;
+    }
+
+    let mut chart = workbook.add_chart(ChartType::Column);
+    let mut series = chart.add_series(Some("=Sheet1!$A$2:$A$5"), Some("=Sheet1!$B$2:$B$5"))?;
+    series.set_name("=Sheet1!$B$1")?;
+    chart.add_title("Quarterly sales")?;
+
+    chartsheet.set_chart(&chart)?;
+    chartsheet.activate();
+    workbook.close()
+}
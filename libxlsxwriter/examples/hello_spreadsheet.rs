@@ -27,7 +27,7 @@ fn main() -> Result<(), xlsxwriter::XlsxError> {
     )?;
     sheet1.merge_range(2, 0, 3, 2, "Hello, world", Some(&format3))?;
 
-    sheet1.set_selection(1, 0, 1, 2);
+    sheet1.set_selection(1, 0, 1, 2)?;
     sheet1.set_tab_color(FormatColor::Cyan);
     workbook.close()
 }
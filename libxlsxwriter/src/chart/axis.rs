@@ -0,0 +1,109 @@
+use crate::{to_c_string, Workbook, XlsxError};
+use std::os::raw::c_char;
+
+/// Struct to represent an Excel chart axis.
+/// This struct is created using the chart.x_axis() or chart.y_axis() functions. It is used in functions that modify a chart axis but the members of the struct aren't modified directly.
+pub struct ChartAxis<'a> {
+    pub(crate) _workbook: &'a Workbook,
+    pub(crate) axis: *mut libxlsxwriter_sys::lxw_chart_axis,
+}
+
+impl<'a> ChartAxis<'a> {
+    /// This function is used to set the number format for the axis tick labels. The format string uses the same syntax as `Format.set_num_format()`:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart_axis-set_num_format-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # write_worksheet(&mut worksheet)?; // write worksheet contents
+    /// # let mut chart = workbook.add_chart(ChartType::Column);
+    /// # chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// chart.y_axis().set_num_format("#,##0")?;
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// # fn write_worksheet(worksheet: &mut Worksheet) -> Result<(), XlsxError> {
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i*10000).into(), None)?;
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_num_format(&mut self, num_format: &str) -> Result<(), XlsxError> {
+        let num_format_vec = to_c_string(num_format)?.into_bytes_with_nul();
+        unsafe {
+            libxlsxwriter_sys::chart_axis_set_num_format(
+                self.axis,
+                num_format_vec.as_ptr() as *const c_char,
+            );
+        }
+        self._workbook.const_str.borrow_mut().push(num_format_vec);
+        Ok(())
+    }
+
+    /// This function is used to set the name (also known as title) for a chart axis. The name can also be a formula such as `=Sheet1!$A$1` to point to a cell in the workbook that contains the name:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart_axis-set_name-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i*10).into(), None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Column);
+    /// # chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// chart.x_axis().set_name("Quarter")?;
+    /// chart.y_axis().set_name("Completion")?;
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_name(&mut self, name: &str) -> Result<(), XlsxError> {
+        let name_vec = to_c_string(name)?.into_bytes_with_nul();
+        unsafe {
+            libxlsxwriter_sys::chart_axis_set_name(self.axis, name_vec.as_ptr() as *const c_char);
+        }
+        self._workbook.const_str.borrow_mut().push(name_vec);
+        Ok(())
+    }
+
+    /// This function is used to set the minimum value for a chart axis:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart_axis-set_min-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i as f64) * 0.2, None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Line);
+    /// # chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// let mut y_axis = chart.y_axis();
+    /// y_axis.set_min(0.);
+    /// y_axis.set_max(1.);
+    /// y_axis.set_num_format("0%")?;
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Value axes support a minimum and maximum value. Category axes only support them for date axes.
+    pub fn set_min(&mut self, min: f64) {
+        unsafe {
+            libxlsxwriter_sys::chart_axis_set_min(self.axis, min);
+        }
+    }
+
+    /// This function is used to set the maximum value for a chart axis. See `set_min()` above.
+    pub fn set_max(&mut self, max: f64) {
+        unsafe {
+            libxlsxwriter_sys::chart_axis_set_max(self.axis, max);
+        }
+    }
+
+    /// This function is used to set the log base of a value axis. The allowable range in Excel is 2 to 1000.
+    pub fn set_log_base(&mut self, log_base: u16) {
+        unsafe {
+            libxlsxwriter_sys::chart_axis_set_log_base(self.axis, log_base);
+        }
+    }
+}
@@ -6,11 +6,14 @@ pub enum ChartType {
     AreaStackedPercent,
     Bar,
     BarStacked,
+    BarStackedPercent,
     Column,
     ColumnStacked,
     ColumnStackedPercent,
     Doughnut,
     Line,
+    LineStacked,
+    LineStackedPercent,
     Pie,
     Scatter,
     ScatterStraight,
@@ -33,6 +36,9 @@ impl ChartType {
             }
             ChartType::Bar => libxlsxwriter_sys::lxw_chart_type_LXW_CHART_BAR,
             ChartType::BarStacked => libxlsxwriter_sys::lxw_chart_type_LXW_CHART_BAR_STACKED,
+            ChartType::BarStackedPercent => {
+                libxlsxwriter_sys::lxw_chart_type_LXW_CHART_BAR_STACKED_PERCENT
+            }
             ChartType::Column => libxlsxwriter_sys::lxw_chart_type_LXW_CHART_COLUMN,
             ChartType::ColumnStacked => libxlsxwriter_sys::lxw_chart_type_LXW_CHART_COLUMN_STACKED,
             ChartType::ColumnStackedPercent => {
@@ -40,6 +46,10 @@ impl ChartType {
             }
             ChartType::Doughnut => libxlsxwriter_sys::lxw_chart_type_LXW_CHART_DOUGHNUT,
             ChartType::Line => libxlsxwriter_sys::lxw_chart_type_LXW_CHART_LINE,
+            ChartType::LineStacked => libxlsxwriter_sys::lxw_chart_type_LXW_CHART_LINE_STACKED,
+            ChartType::LineStackedPercent => {
+                libxlsxwriter_sys::lxw_chart_type_LXW_CHART_LINE_STACKED_PERCENT
+            }
             ChartType::Pie => libxlsxwriter_sys::lxw_chart_type_LXW_CHART_PIE,
             ChartType::Scatter => libxlsxwriter_sys::lxw_chart_type_LXW_CHART_SCATTER,
             ChartType::ScatterStraight => {
@@ -416,3 +426,185 @@ impl ChartMarkerType {
         value as u8
     }
 }
+
+/// The position of the chart legend, used with `Chart::set_legend_position()`.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum ChartLegendPosition {
+    /// Turn off the chart legend.
+    None,
+    Right,
+    Left,
+    Top,
+    Bottom,
+    TopRight,
+    /// Chart legend overlaid at right side. The legend is drawn over the plot area.
+    OverlayRight,
+    /// Chart legend overlaid at left side. The legend is drawn over the plot area.
+    OverlayLeft,
+    /// Chart legend overlaid at top right. The legend is drawn over the plot area.
+    OverlayTopRight,
+}
+
+impl ChartLegendPosition {
+    pub(crate) fn value(self) -> u8 {
+        let value = match self {
+            ChartLegendPosition::None => {
+                libxlsxwriter_sys::lxw_chart_legend_position_LXW_CHART_LEGEND_NONE
+            }
+            ChartLegendPosition::Right => {
+                libxlsxwriter_sys::lxw_chart_legend_position_LXW_CHART_LEGEND_RIGHT
+            }
+            ChartLegendPosition::Left => {
+                libxlsxwriter_sys::lxw_chart_legend_position_LXW_CHART_LEGEND_LEFT
+            }
+            ChartLegendPosition::Top => {
+                libxlsxwriter_sys::lxw_chart_legend_position_LXW_CHART_LEGEND_TOP
+            }
+            ChartLegendPosition::Bottom => {
+                libxlsxwriter_sys::lxw_chart_legend_position_LXW_CHART_LEGEND_BOTTOM
+            }
+            ChartLegendPosition::TopRight => {
+                libxlsxwriter_sys::lxw_chart_legend_position_LXW_CHART_LEGEND_TOP_RIGHT
+            }
+            ChartLegendPosition::OverlayRight => {
+                libxlsxwriter_sys::lxw_chart_legend_position_LXW_CHART_LEGEND_OVERLAY_RIGHT
+            }
+            ChartLegendPosition::OverlayLeft => {
+                libxlsxwriter_sys::lxw_chart_legend_position_LXW_CHART_LEGEND_OVERLAY_LEFT
+            }
+            ChartLegendPosition::OverlayTopRight => {
+                libxlsxwriter_sys::lxw_chart_legend_position_LXW_CHART_LEGEND_OVERLAY_TOP_RIGHT
+            }
+        };
+        value as u8
+    }
+}
+
+/// Position of the data labels of a chart series. Not all positions are supported by all chart types.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum ChartLabelPosition {
+    /// Series data label position: default position.
+    Default,
+    Center,
+    Right,
+    Left,
+    Above,
+    Below,
+    InsideBase,
+    InsideEnd,
+    OutsideEnd,
+    /// Series data label position: best fit. Only for pie charts.
+    BestFit,
+}
+
+impl ChartLabelPosition {
+    pub(crate) fn value(self) -> u8 {
+        let value = match self {
+            ChartLabelPosition::Default => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_DEFAULT
+            }
+            ChartLabelPosition::Center => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_CENTER
+            }
+            ChartLabelPosition::Right => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_RIGHT
+            }
+            ChartLabelPosition::Left => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_LEFT
+            }
+            ChartLabelPosition::Above => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_ABOVE
+            }
+            ChartLabelPosition::Below => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_BELOW
+            }
+            ChartLabelPosition::InsideBase => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_INSIDE_BASE
+            }
+            ChartLabelPosition::InsideEnd => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_INSIDE_END
+            }
+            ChartLabelPosition::OutsideEnd => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_OUTSIDE_END
+            }
+            ChartLabelPosition::BestFit => {
+                libxlsxwriter_sys::lxw_chart_label_position_LXW_CHART_LABEL_POSITION_BEST_FIT
+            }
+        };
+        value as u8
+    }
+}
+
+/// Separator between the data label items of a chart series.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum ChartLabelSeparator {
+    Comma,
+    Semicolon,
+    Period,
+    Newline,
+    Space,
+}
+
+impl ChartLabelSeparator {
+    pub(crate) fn value(self) -> u8 {
+        let value = match self {
+            ChartLabelSeparator::Comma => {
+                libxlsxwriter_sys::lxw_chart_label_separator_LXW_CHART_LABEL_SEPARATOR_COMMA
+            }
+            ChartLabelSeparator::Semicolon => {
+                libxlsxwriter_sys::lxw_chart_label_separator_LXW_CHART_LABEL_SEPARATOR_SEMICOLON
+            }
+            ChartLabelSeparator::Period => {
+                libxlsxwriter_sys::lxw_chart_label_separator_LXW_CHART_LABEL_SEPARATOR_PERIOD
+            }
+            ChartLabelSeparator::Newline => {
+                libxlsxwriter_sys::lxw_chart_label_separator_LXW_CHART_LABEL_SEPARATOR_NEWLINE
+            }
+            ChartLabelSeparator::Space => {
+                libxlsxwriter_sys::lxw_chart_label_separator_LXW_CHART_LABEL_SEPARATOR_SPACE
+            }
+        };
+        value as u8
+    }
+}
+
+/// Type of a chart series trendline, used with `ChartSeries::set_trendline()`.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum ChartTrendlineType {
+    /// Exponential trendline.
+    Exp,
+    Linear,
+    /// Logarithmic trendline.
+    Log,
+    /// Moving average trendline. The period is given as the trendline value.
+    Average,
+    /// Polynomial trendline. The order is given as the trendline value.
+    Poly,
+    Power,
+}
+
+impl ChartTrendlineType {
+    pub(crate) fn value(self) -> u8 {
+        let value = match self {
+            ChartTrendlineType::Exp => {
+                libxlsxwriter_sys::lxw_chart_trendline_type_LXW_CHART_TRENDLINE_TYPE_EXP
+            }
+            ChartTrendlineType::Linear => {
+                libxlsxwriter_sys::lxw_chart_trendline_type_LXW_CHART_TRENDLINE_TYPE_LINEAR
+            }
+            ChartTrendlineType::Log => {
+                libxlsxwriter_sys::lxw_chart_trendline_type_LXW_CHART_TRENDLINE_TYPE_LOG
+            }
+            ChartTrendlineType::Average => {
+                libxlsxwriter_sys::lxw_chart_trendline_type_LXW_CHART_TRENDLINE_TYPE_AVERAGE
+            }
+            ChartTrendlineType::Poly => {
+                libxlsxwriter_sys::lxw_chart_trendline_type_LXW_CHART_TRENDLINE_TYPE_POLY
+            }
+            ChartTrendlineType::Power => {
+                libxlsxwriter_sys::lxw_chart_trendline_type_LXW_CHART_TRENDLINE_TYPE_POWER
+            }
+        };
+        value as u8
+    }
+}
@@ -1,11 +1,13 @@
+mod axis;
 mod constants;
 mod series;
 mod structs;
 
+pub use self::axis::*;
 pub use self::constants::*;
 pub use self::series::*;
 pub use self::structs::*;
-use super::{convert_str, Workbook};
+use super::{to_c_string, Workbook, WorksheetCol, WorksheetRow, XlsxError};
 use std::os::raw::c_char;
 
 /// The Chart object represents an Excel chart. It provides functions for adding data series to the chart and for configuring the chart.
@@ -18,9 +20,9 @@ use std::os::raw::c_char;
 /// let mut worksheet = workbook.add_worksheet(None)?;
 /// write_worksheet(&mut worksheet)?; // write worksheet contents
 /// let mut chart = workbook.add_chart(ChartType::Column);
-/// chart.add_series(None, Some("=Sheet1!$A$1:$A$5"));
-/// chart.add_series(None, Some("=Sheet1!$B$1:$B$5"));
-/// chart.add_series(None, Some("=Sheet1!$C$1:$C$5"));
+/// chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+/// chart.add_series(None, Some("=Sheet1!$B$1:$B$5"))?;
+/// chart.add_series(None, Some("=Sheet1!$C$1:$C$5"))?;
 /// worksheet.insert_chart(1, 3, &chart)?;
 /// workbook.close()
 /// # }
@@ -43,12 +45,70 @@ use std::os::raw::c_char;
 /// Add one or more data series to the chart which refers to data in the workbook using Chart.add_series().
 /// Configure the chart with the other available functions shown below.
 /// Insert the chart into a worksheet using Worksheet.insert_chart().
+///
+/// A chart borrows the workbook that created it, so it can't be used once the workbook is closed:
+/// ```compile_fail,E0505
+/// # use xlsxwriter::*;
+/// # fn main() -> Result<(), XlsxError> {
+/// let workbook = Workbook::new("test-chart-lifetime-1.xlsx");
+/// let mut chart = workbook.add_chart(ChartType::Column);
+/// workbook.close()?;
+/// chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+/// # Ok(())
+/// # }
+/// ```
+/// A chart can only be inserted into a worksheet or chartsheet of the workbook that created it. Otherwise an error is returned.
 pub struct Chart<'a> {
     pub(crate) _workbook: &'a Workbook,
     pub(crate) chart: *mut libxlsxwriter_sys::lxw_chart,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
 }
 
+/// Default width of a chart in pixels.
+pub(crate) const DEFAULT_CHART_WIDTH: u32 = 480;
+/// Default height of a chart in pixels.
+pub(crate) const DEFAULT_CHART_HEIGHT: u32 = 288;
+
 impl<'a> Chart<'a> {
+    /// This function is used to set the size of the chart in pixels. The default size is 480 x 288 pixels. The size is applied when the chart is inserted into a worksheet, so it has to be called before `Worksheet.insert_chart()`:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart-set_size-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i*10).into(), None)?;
+    /// # }
+    /// let mut chart = workbook.add_chart(ChartType::Column);
+    /// chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// chart.set_size(720, 400);
+    /// worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Return an error if the chart wasn't created by `workbook`.
+    pub(crate) fn check_workbook(&self, workbook: &Workbook) -> Result<(), XlsxError> {
+        if std::ptr::eq(self._workbook, workbook) {
+            Ok(())
+        } else {
+            Err(XlsxError::new(crate::error::CHART_FROM_OTHER_WORKBOOK))
+        }
+    }
+
+    /// Scale factors that turn the default chart size into the size given by `set_size()`.
+    pub(crate) fn size_scale(&self) -> (f64, f64) {
+        (
+            f64::from(self.width) / f64::from(DEFAULT_CHART_WIDTH),
+            f64::from(self.height) / f64::from(DEFAULT_CHART_HEIGHT),
+        )
+    }
+
     /// In Excel a chart **series** is a collection of information that defines which data is plotted such as the categories and values. It is also used to define the formatting for the data.
     ///
     /// For an libxlsxwriter chart object the chart_add_series() function is used to set the categories and values of the series:
@@ -59,7 +119,7 @@ impl<'a> Chart<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// chart.add_series(Some("=Sheet1!$A$1:$A$5"), Some("=Sheet1!$B$1:$B$5"));
+    /// chart.add_series(Some("=Sheet1!$A$1:$A$5"), Some("=Sheet1!$B$1:$B$5"))?;
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
     /// # }
@@ -81,7 +141,7 @@ impl<'a> Chart<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// chart.add_series(None, Some("=Sheet1!$B$1:$B$5"));
+    /// chart.add_series(None, Some("=Sheet1!$B$1:$B$5"))?;
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
     /// # }
@@ -103,9 +163,9 @@ impl<'a> Chart<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// let mut series = chart.add_series(None, None);
-    /// series.set_categories("Sheet1", 0, 0, 4, 0); // "=Sheet1!$A$1:$A$5"
-    /// series.set_values("Sheet1", 0, 1, 4, 1);     // "=Sheet1!$B$1:$B$5"
+    /// let mut series = chart.add_series(None, None)?;
+    /// series.set_categories("Sheet1", 0, 0, 4, 0)?; // "=Sheet1!$A$1:$A$5"
+    /// series.set_values("Sheet1", 0, 1, 4, 1)?;     // "=Sheet1!$B$1:$B$5"
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
     /// # }
@@ -128,9 +188,9 @@ impl<'a> Chart<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// chart.add_series(None, Some("=Sheet1!$A$1:$A$5"));
-    /// chart.add_series(None, Some("=Sheet1!$B$1:$B$5"));
-    /// chart.add_series(None, Some("=Sheet1!$C$1:$C$5"));
+    /// chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// chart.add_series(None, Some("=Sheet1!$B$1:$B$5"))?;
+    /// chart.add_series(None, Some("=Sheet1!$C$1:$C$5"))?;
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
     /// # }
@@ -151,7 +211,7 @@ impl<'a> Chart<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// chart.add_series(Some("=(Sheet1!$A$1:$A$5,Sheet1!$A$10:$A$18)"), Some("=(Sheet1!$B$1:$B$5,Sheet1!$B$10:$B$18)"));
+    /// chart.add_series(Some("=(Sheet1!$A$1:$A$5,Sheet1!$A$10:$A$18)"), Some("=(Sheet1!$B$1:$B$5,Sheet1!$B$10:$B$18)"))?;
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
     /// # }
@@ -168,9 +228,13 @@ impl<'a> Chart<'a> {
         &mut self,
         categories: Option<&str>,
         values: Option<&str>,
-    ) -> ChartSeries<'a> {
-        let categories_vec = categories.map(convert_str);
-        let values_vec = values.map(convert_str);
+    ) -> Result<ChartSeries<'a>, XlsxError> {
+        let categories_vec = categories
+            .map(|x| to_c_string(x).map(|y| y.into_bytes_with_nul()))
+            .transpose()?;
+        let values_vec = values
+            .map(|x| to_c_string(x).map(|y| y.into_bytes_with_nul()))
+            .transpose()?;
         let mut const_str = self._workbook.const_str.borrow_mut();
         let series = unsafe {
             libxlsxwriter_sys::chart_add_series(
@@ -191,21 +255,200 @@ impl<'a> Chart<'a> {
         if let Some(x) = values_vec {
             const_str.push(x);
         }
-        ChartSeries {
+        Ok(ChartSeries {
             _workbook: self._workbook,
             chart_series: series,
+        })
+    }
+
+    /// This function is the same as `add_series()`, but takes the categories and values as `(sheet_name, first_row, first_col, last_row, last_col)`
+    /// instead of range formulas. The absolute references are built by libxlsxwriter, which also quotes sheet names that need it:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart-add_series_range-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(Some("Sales Data"))?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_string(i, 0, &format!("value {}", i + 1), None)?;
+    /// #     worksheet.write_number(i, 1, (i*10 + 2).into(), None)?;
+    /// # }
+    /// let mut chart = workbook.add_chart(ChartType::Column);
+    /// // The same as add_series(Some("='Sales Data'!$A$1:$A$5"), Some("='Sales Data'!$B$1:$B$5"))
+    /// chart.add_series_range(Some(("Sales Data", 0, 0, 4, 0)), ("Sales Data", 0, 1, 4, 1))?;
+    /// worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// See `ChartSeries.set_categories()` and `ChartSeries.set_values()`, which this function calls.
+    pub fn add_series_range(
+        &mut self,
+        categories: Option<(&str, WorksheetRow, WorksheetCol, WorksheetRow, WorksheetCol)>,
+        values: (&str, WorksheetRow, WorksheetCol, WorksheetRow, WorksheetCol),
+    ) -> Result<ChartSeries<'a>, XlsxError> {
+        let mut series = self.add_series(None, None)?;
+        if let Some((sheet_name, first_row, first_col, last_row, last_col)) = categories {
+            series.set_categories(sheet_name, first_row, first_col, last_row, last_col)?;
         }
+        let (sheet_name, first_row, first_col, last_row, last_col) = values;
+        series.set_values(sheet_name, first_row, first_col, last_row, last_col)?;
+        Ok(series)
     }
 
     /// The chart_title_set_name() function sets the name (title) for the chart. The name is displayed above the chart.
     /// The name parameter can also be a formula such as `=Sheet1!$A$1` to point to a cell in the workbook that contains the name.
     /// The Excel default is to have no chart title.
-    pub fn add_title(&mut self, title: &str) {
-        let title_vec = convert_str(title);
+    ///
+    /// This function is the same as `set_title_name()`.
+    pub fn add_title(&mut self, title: &str) -> Result<(), XlsxError> {
+        self.set_title_name(title)
+    }
+
+    /// This function sets the name (title) for the chart. The name is displayed above the chart:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart-set_title_name-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i*10).into(), None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Column);
+    /// # chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// chart.set_title_name("Year End Results")?;
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// The name parameter can also be a formula such as `=Sheet1!$A$1` to point to a cell in the workbook that contains the name.
+    /// The Excel default is to have no chart title for a chart with a single series, and to use the series name otherwise.
+    pub fn set_title_name(&mut self, title: &str) -> Result<(), XlsxError> {
+        let title_vec = to_c_string(title)?.into_bytes_with_nul();
         let mut const_str = self._workbook.const_str.borrow_mut();
         unsafe {
             libxlsxwriter_sys::chart_title_set_name(self.chart, title_vec.as_ptr() as *const c_char)
         }
         const_str.push(title_vec);
+        Ok(())
+    }
+
+    /// This function can be used to set a chart title range and is an alternative to using `set_title_name()` and a string formula.
+    /// The title is updated when the referenced cell changes:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart-set_title_name_range-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # worksheet.write_string(0, 0, "Sales", None)?;
+    /// # for i in 1..6 {
+    /// #     worksheet.write_number(i, 0, (i*10).into(), None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Column);
+    /// # chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// chart.set_title_name_range("Sheet1", 0, 0)?; // =Sheet1!$A$1
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_title_name_range(
+        &mut self,
+        sheet_name: &str,
+        row: WorksheetRow,
+        column: WorksheetCol,
+    ) -> Result<(), XlsxError> {
+        let sheet_name_vec = to_c_string(sheet_name)?.into_bytes_with_nul();
+        unsafe {
+            libxlsxwriter_sys::chart_title_set_name_range(
+                self.chart,
+                sheet_name_vec.as_ptr() as *const c_char,
+                row,
+                column,
+            );
+        }
+        self._workbook.const_str.borrow_mut().push(sheet_name_vec);
+        Ok(())
+    }
+
+    /// This function turns off the automatic chart title that Excel adds to charts with a single series.
+    pub fn set_title_none(&mut self) {
+        unsafe {
+            libxlsxwriter_sys::chart_title_off(self.chart);
+        }
+    }
+
+    /// This function is used to set the chart legend display position:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart-set_legend_position-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i*10).into(), None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Column);
+    /// # chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// chart.set_legend_position(ChartLegendPosition::Bottom);
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// The default legend position is `Right`.
+    pub fn set_legend_position(&mut self, position: ChartLegendPosition) {
+        unsafe {
+            libxlsxwriter_sys::chart_legend_set_position(self.chart, position.value());
+        }
+    }
+
+    /// This function turns off the chart legend. It is the same as `set_legend_position(ChartLegendPosition::None)`.
+    pub fn delete_legend(&mut self) {
+        self.set_legend_position(ChartLegendPosition::None);
+    }
+
+    /// This function returns the X axis of the chart so that it can be configured with the `ChartAxis` functions.
+    ///
+    /// For most chart types the X axis is the category axis. For scatter charts it is a value axis.
+    pub fn x_axis(&mut self) -> ChartAxis<'a> {
+        ChartAxis {
+            _workbook: self._workbook,
+            axis: unsafe { (*self.chart).x_axis },
+        }
+    }
+
+    /// This function returns the Y axis of the chart so that it can be configured with the `ChartAxis` functions.
+    ///
+    /// For most chart types the Y axis is the value axis.
+    pub fn y_axis(&mut self) -> ChartAxis<'a> {
+        ChartAxis {
+            _workbook: self._workbook,
+            axis: unsafe { (*self.chart).y_axis },
+        }
+    }
+
+    /// This function combines two charts of different types so that they are displayed as one chart, for example a column chart with a line chart overlaid on it:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart-combine-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i*10).into(), None)?;
+    /// #     worksheet.write_number(i, 1, (i*10 + 5).into(), None)?;
+    /// # }
+    /// let mut column_chart = workbook.add_chart(ChartType::Column);
+    /// column_chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// let mut line_chart = workbook.add_chart(ChartType::Line);
+    /// line_chart.add_series(None, Some("=Sheet1!$B$1:$B$5"))?;
+    /// column_chart.combine(&line_chart)?;
+    /// worksheet.insert_chart(1, 3, &column_chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Only the primary chart is inserted into the worksheet. The combined chart uses the axes of the primary chart and its own axes
+    /// can't be configured. Both charts must have been created by the same workbook, otherwise an error is returned.
+    pub fn combine(&mut self, combined_chart: &Chart) -> Result<(), XlsxError> {
+        combined_chart.check_workbook(self._workbook)?;
+        unsafe {
+            libxlsxwriter_sys::chart_combine(self.chart, combined_chart.chart);
+        }
+        Ok(())
     }
 }
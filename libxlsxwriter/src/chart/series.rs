@@ -1,6 +1,6 @@
 use super::constants::*;
 use super::structs::*;
-use crate::{convert_bool, convert_str, Workbook, WorksheetCol, WorksheetRow};
+use crate::{convert_bool, to_c_string, Workbook, WorksheetCol, WorksheetRow, XlsxError};
 use std::os::raw::c_char;
 
 /// Struct to represent an Excel chart data series.
@@ -21,9 +21,9 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// let mut series = chart.add_series(None, None);
-    /// series.set_categories("Sheet1", 0, 0, 4, 0); // "=Sheet1!$A$1:$A$5"
-    /// series.set_values("Sheet1", 0, 1, 4, 1);     // "=Sheet1!$B$1:$B$5"
+    /// let mut series = chart.add_series(None, None)?;
+    /// series.set_categories("Sheet1", 0, 0, 4, 0)?; // "=Sheet1!$A$1:$A$5"
+    /// series.set_values("Sheet1", 0, 1, 4, 1)?;     // "=Sheet1!$B$1:$B$5"
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
     /// # }
@@ -42,8 +42,8 @@ impl<'a> ChartSeries<'a> {
         first_column: WorksheetCol,
         last_row: WorksheetRow,
         last_column: WorksheetCol,
-    ) {
-        let sheet_name_vec = convert_str(sheet_name);
+    ) -> Result<(), XlsxError> {
+        let sheet_name_vec = to_c_string(sheet_name)?.into_bytes_with_nul();
         unsafe {
             libxlsxwriter_sys::chart_series_set_categories(
                 self.chart_series,
@@ -55,6 +55,7 @@ impl<'a> ChartSeries<'a> {
             );
         }
         self._workbook.const_str.borrow_mut().push(sheet_name_vec);
+        Ok(())
     }
 
     /// The categories and values of a chart data series are generally set using the `Chart.add_series()` function and Excel range formulas like "=Sheet1!$A$2:$A$7".
@@ -67,8 +68,8 @@ impl<'a> ChartSeries<'a> {
         first_column: WorksheetCol,
         last_row: WorksheetRow,
         last_column: WorksheetCol,
-    ) {
-        let sheet_name_vec = convert_str(sheet_name);
+    ) -> Result<(), XlsxError> {
+        let sheet_name_vec = to_c_string(sheet_name)?.into_bytes_with_nul();
         unsafe {
             libxlsxwriter_sys::chart_series_set_values(
                 self.chart_series,
@@ -80,6 +81,7 @@ impl<'a> ChartSeries<'a> {
             );
         }
         self._workbook.const_str.borrow_mut().push(sheet_name_vec);
+        Ok(())
     }
 
     /// This function is used to set the name for a chart data series. The series name in Excel is displayed in the chart legend and in the formula bar. The name property is optional and if it isn't supplied it will default to `Series 1..n`.
@@ -91,8 +93,8 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// let mut series = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// series.set_name("Quarterly budget data");
+    /// let mut series = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// series.set_name("Quarterly budget data")?;
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
     /// # }
@@ -117,8 +119,8 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// let mut series = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// series.set_name("=Sheet1!$A$1:$A$1");
+    /// let mut series = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// series.set_name("=Sheet1!$A$1:$A$1")?;
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
     /// # }
@@ -134,8 +136,8 @@ impl<'a> ChartSeries<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_name(&mut self, name: &str) {
-        let name_vec = convert_str(name);
+    pub fn set_name(&mut self, name: &str) -> Result<(), XlsxError> {
+        let name_vec = to_c_string(name)?.into_bytes_with_nul();
         unsafe {
             libxlsxwriter_sys::chart_series_set_name(
                 self.chart_series,
@@ -143,6 +145,7 @@ impl<'a> ChartSeries<'a> {
             );
         }
         self._workbook.const_str.borrow_mut().push(name_vec);
+        Ok(())
     }
 
     /// The `ChartSeries.set_name_range()` function can be used to set a series name range and is an alternative to using `ChartSeries.set_name()` and a string formula:
@@ -153,8 +156,8 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// let mut series = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"));
-    /// series.set_name_range("Sheet1", 0, 1); // =Sheet1!$B$1
+    /// let mut series = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"))?;
+    /// series.set_name_range("Sheet1", 0, 1)?; // =Sheet1!$B$1
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
     /// # }
@@ -170,8 +173,13 @@ impl<'a> ChartSeries<'a> {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_name_range(&mut self, sheet_name: &str, row: WorksheetRow, column: WorksheetCol) {
-        let sheet_name_vec = convert_str(sheet_name);
+    pub fn set_name_range(
+        &mut self,
+        sheet_name: &str,
+        row: WorksheetRow,
+        column: WorksheetCol,
+    ) -> Result<(), XlsxError> {
+        let sheet_name_vec = to_c_string(sheet_name)?.into_bytes_with_nul();
         unsafe {
             libxlsxwriter_sys::chart_series_set_name_range(
                 self.chart_series,
@@ -181,6 +189,7 @@ impl<'a> ChartSeries<'a> {
             );
         }
         self._workbook.const_str.borrow_mut().push(sheet_name_vec);
+        Ok(())
     }
 
     /// Set the line/border properties of a chart series:
@@ -191,9 +200,9 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// let mut series2 = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"));
-    /// let mut series3 = chart.add_series(None, Some("=Sheet1!$C$2:$C$6"));
+    /// let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// let mut series2 = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"))?;
+    /// let mut series3 = chart.add_series(None, Some("=Sheet1!$C$2:$C$6"))?;
     /// let mut chart_line = ChartLine::new();
     /// chart_line.color = FormatColor::Red;
     /// series1.set_line(&chart_line);
@@ -229,9 +238,9 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// # let mut series2 = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"));
-    /// # let mut series3 = chart.add_series(None, Some("=Sheet1!$C$2:$C$6"));
+    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// # let mut series2 = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"))?;
+    /// # let mut series3 = chart.add_series(None, Some("=Sheet1!$C$2:$C$6"))?;
     /// let mut chart_fill_1 = ChartFill::new();
     /// chart_fill_1.color = FormatColor::Red;
     /// let mut chart_fill_2 = ChartFill::new();
@@ -271,12 +280,12 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// # let mut series2 = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"));
-    /// # let mut series3 = chart.add_series(None, Some("=Sheet1!$C$2:$C$6"));
-    /// # series1.set_name("=Sheet1!$A$1");
-    /// # series2.set_name("=Sheet1!$B$1");
-    /// # series3.set_name("=Sheet1!$C$1");
+    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// # let mut series2 = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"))?;
+    /// # let mut series3 = chart.add_series(None, Some("=Sheet1!$C$2:$C$6"))?;
+    /// # series1.set_name("=Sheet1!$A$1")?;
+    /// # series2.set_name("=Sheet1!$B$1")?;
+    /// # series3.set_name("=Sheet1!$C$1")?;
     /// # let mut chart_fill_1 = ChartFill::new();
     /// # chart_fill_1.color = FormatColor::Red;
     /// # let mut chart_fill_2 = ChartFill::new();
@@ -319,10 +328,10 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Column);
-    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// # let mut series2 = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"));
-    /// # series1.set_name("=Sheet1!$A$1");
-    /// # series2.set_name("=Sheet1!$B$1");
+    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// # let mut series2 = chart.add_series(None, Some("=Sheet1!$B$2:$B$6"))?;
+    /// # series1.set_name("=Sheet1!$A$1")?;
+    /// # series2.set_name("=Sheet1!$B$1")?;
     /// let pattern1 = ChartPattern::new(FormatColor::Custom(0x804000), FormatColor::Custom(0xC68C53), ChartPatternType::Shingle);
     /// series1.set_pattern(&pattern1);
     /// let pattern2 = ChartPattern::new(FormatColor::Custom(0xB30000), FormatColor::Custom(0xFF6666), ChartPatternType::HorizontalBrick);
@@ -356,8 +365,8 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Line);
-    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// # series1.set_name("=Sheet1!$A$1");
+    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// # series1.set_name("=Sheet1!$A$1")?;
     /// series1.set_marker_type(ChartMarkerType::MarkerDiamond);
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
@@ -386,8 +395,8 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Line);
-    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// # series1.set_name("=Sheet1!$A$1");
+    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// # series1.set_name("=Sheet1!$A$1")?;
     /// series1.set_marker_type(ChartMarkerType::MarkerDiamond);
     /// series1.set_marker_size(10);
     /// # worksheet.insert_chart(1, 3, &chart)?;
@@ -406,6 +415,27 @@ impl<'a> ChartSeries<'a> {
         unsafe { libxlsxwriter_sys::chart_series_set_marker_size(self.chart_series, maker_size) }
     }
 
+    /// This function sets the marker type and size of a series in one call. It is a shortcut for `set_marker_type()` followed by `set_marker_size()`:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart_series-set_marker-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i * 7 % 5 * 10).into(), None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Line);
+    /// let mut series = chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// series.set_marker(ChartMarkerType::MarkerDiamond, 8);
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_marker(&mut self, marker_type: ChartMarkerType, marker_size: u8) {
+        self.set_marker_type(marker_type);
+        self.set_marker_size(marker_size);
+    }
+
     /// Set the line/border properties of a chart marker.
     /// ```rust
     /// # use xlsxwriter::*;
@@ -414,8 +444,8 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Line);
-    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// # series1.set_name("=Sheet1!$A$1");
+    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// # series1.set_name("=Sheet1!$A$1")?;
     /// series1.set_marker_type(ChartMarkerType::MarkerDiamond);
     /// let mut marker_line = ChartLine::new();
     /// marker_line.color = FormatColor::Red;
@@ -450,8 +480,8 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Line);
-    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// # series1.set_name("=Sheet1!$A$1");
+    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// # series1.set_name("=Sheet1!$A$1")?;
     /// series1.set_marker_type(ChartMarkerType::MarkerDiamond);
     /// let mut marker_line = ChartLine::new();
     /// marker_line.color = FormatColor::Red;
@@ -491,8 +521,8 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Line);
-    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// # series1.set_name("=Sheet1!$A$1");
+    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// # series1.set_name("=Sheet1!$A$1")?;
     /// series1.set_smooth(true);
     /// # worksheet.insert_chart(1, 3, &chart)?;
     /// # workbook.close()
@@ -521,8 +551,8 @@ impl<'a> ChartSeries<'a> {
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// # write_worksheet(&mut worksheet)?; // write worksheet contents
     /// # let mut chart = workbook.add_chart(ChartType::Line);
-    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"));
-    /// # series1.set_name("=Sheet1!$A$1");
+    /// # let mut series1 = chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    /// # series1.set_name("=Sheet1!$A$1")?;
     /// series1.set_marker_type(ChartMarkerType::MarkerDiamond);
     /// series1.set_labels();
     /// # worksheet.insert_chart(1, 3, &chart)?;
@@ -541,4 +571,176 @@ impl<'a> ChartSeries<'a> {
     pub fn set_labels(&mut self) {
         unsafe { libxlsxwriter_sys::chart_series_set_labels(self.chart_series) }
     }
+
+    /// This function is used to set the display options of the data labels. By default only the value is shown:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart_series-set_labels_options-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_string(i, 0, &format!("Item {}", i + 1), None)?;
+    /// #     worksheet.write_number(i, 1, (i * 10 + 2).into(), None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Column);
+    /// let mut series = chart.add_series(Some("=Sheet1!$A$1:$A$5"), Some("=Sheet1!$B$1:$B$5"))?;
+    /// series.set_labels();
+    /// series.set_labels_options(false, true, true);
+    /// series.set_labels_separator(ChartLabelSeparator::Newline);
+    /// series.set_labels_position(ChartLabelPosition::OutsideEnd);
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_labels_options(&mut self, show_name: bool, show_category: bool, show_value: bool) {
+        unsafe {
+            libxlsxwriter_sys::chart_series_set_labels_options(
+                self.chart_series,
+                convert_bool(show_name),
+                convert_bool(show_category),
+                convert_bool(show_value),
+            )
+        }
+    }
+
+    /// This function is used to change the separator between multiple data label items. The default is a comma.
+    pub fn set_labels_separator(&mut self, separator: ChartLabelSeparator) {
+        unsafe {
+            libxlsxwriter_sys::chart_series_set_labels_separator(
+                self.chart_series,
+                separator.value(),
+            )
+        }
+    }
+
+    /// This function is used to set the position of the data labels.
+    pub fn set_labels_position(&mut self, position: ChartLabelPosition) {
+        unsafe {
+            libxlsxwriter_sys::chart_series_set_labels_position(self.chart_series, position.value())
+        }
+    }
+
+    /// This function is used to turn on leader lines for the data labels of a series. It is mainly used for pie charts.
+    pub fn set_labels_leader_line(&mut self) {
+        unsafe { libxlsxwriter_sys::chart_series_set_labels_leader_line(self.chart_series) }
+    }
+
+    /// This function is used to show the legend key in the data labels.
+    pub fn set_labels_legend(&mut self) {
+        unsafe { libxlsxwriter_sys::chart_series_set_labels_legend(self.chart_series) }
+    }
+
+    /// This function is used to display the data labels of a pie or doughnut chart series as a percentage:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart_series-set_labels_percentage-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i * 10 + 2).into(), None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Pie);
+    /// let mut series = chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// series.set_labels();
+    /// series.set_labels_options(false, false, false);
+    /// series.set_labels_percentage();
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_labels_percentage(&mut self) {
+        unsafe { libxlsxwriter_sys::chart_series_set_labels_percentage(self.chart_series) }
+    }
+
+    /// This function is used to set the number format of the data labels. The format string uses the same syntax as `Format.set_num_format()`.
+    pub fn set_labels_num_format(&mut self, num_format: &str) -> Result<(), XlsxError> {
+        let num_format_vec = to_c_string(num_format)?.into_bytes_with_nul();
+        unsafe {
+            libxlsxwriter_sys::chart_series_set_labels_num_format(
+                self.chart_series,
+                num_format_vec.as_ptr() as *const c_char,
+            );
+        }
+        self._workbook.const_str.borrow_mut().push(num_format_vec);
+        Ok(())
+    }
+
+    /// This function is used to add a trendline to a chart data series. The `value` is the order of a polynomial trendline (2 to 6) or the period of a moving average trendline (2 or more). It is ignored by the other trendline types:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-chart_series-set_trendline-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..6 {
+    /// #     worksheet.write_number(i, 0, (i * i + 3).into(), None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Line);
+    /// let mut series = chart.add_series(None, Some("=Sheet1!$A$1:$A$6"))?;
+    /// series.set_trendline(ChartTrendlineType::Poly, 2);
+    /// series.set_trendline_equation();
+    /// series.set_trendline_r_squared();
+    /// # worksheet.insert_chart(1, 3, &chart)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_trendline(&mut self, trendline_type: ChartTrendlineType, value: u8) {
+        unsafe {
+            libxlsxwriter_sys::chart_series_set_trendline(
+                self.chart_series,
+                trendline_type.value(),
+                value,
+            )
+        }
+    }
+
+    /// This function is used to extend a trendline `forward` and/or `backward` by the given number of periods. It isn't available for moving average trendlines.
+    pub fn set_trendline_forecast(&mut self, forward: f64, backward: f64) {
+        unsafe {
+            libxlsxwriter_sys::chart_series_set_trendline_forecast(
+                self.chart_series,
+                forward,
+                backward,
+            )
+        }
+    }
+
+    /// This function is used to display the equation of the trendline on the chart. It isn't available for moving average trendlines.
+    pub fn set_trendline_equation(&mut self) {
+        unsafe { libxlsxwriter_sys::chart_series_set_trendline_equation(self.chart_series) }
+    }
+
+    /// This function is used to display the R-squared value of the trendline on the chart. It isn't available for moving average trendlines.
+    pub fn set_trendline_r_squared(&mut self) {
+        unsafe { libxlsxwriter_sys::chart_series_set_trendline_r_squared(self.chart_series) }
+    }
+
+    /// This function is used to set the point where the trendline crosses the Y axis. It is only available for exponential, linear and polynomial trendlines.
+    pub fn set_trendline_intercept(&mut self, intercept: f64) {
+        unsafe {
+            libxlsxwriter_sys::chart_series_set_trendline_intercept(self.chart_series, intercept)
+        }
+    }
+
+    /// This function is used to set the name of the trendline shown in the chart legend.
+    pub fn set_trendline_name(&mut self, name: &str) -> Result<(), XlsxError> {
+        let name_vec = to_c_string(name)?.into_bytes_with_nul();
+        unsafe {
+            libxlsxwriter_sys::chart_series_set_trendline_name(
+                self.chart_series,
+                name_vec.as_ptr() as *const c_char,
+            );
+        }
+        self._workbook.const_str.borrow_mut().push(name_vec);
+        Ok(())
+    }
+
+    /// Set the line properties of the trendline.
+    pub fn set_trendline_line(&mut self, line: &ChartLine) {
+        unsafe {
+            libxlsxwriter_sys::chart_series_set_trendline_line(
+                self.chart_series,
+                &mut line.value(),
+            );
+        }
+    }
 }
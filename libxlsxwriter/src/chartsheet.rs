@@ -0,0 +1,56 @@
+use super::{Chart, FormatColor, Workbook, XlsxError};
+
+/// The Chartsheet object represents an Excel chartsheet. It handles operations such as adding a chart and setting the page layout.
+///
+/// A chartsheet is a special type of worksheet that contains only a chart and which is displayed as a full page. A Chartsheet object isn't created directly. Instead a chartsheet is created by calling the `workbook.add_chartsheet()` function from a [Workbook](struct.Workbook.html) object:
+/// ```rust
+/// use xlsxwriter::*;
+/// # fn main() -> Result<(), XlsxError> {
+/// let workbook = Workbook::new("test-chartsheet.xlsx");
+/// let mut worksheet = workbook.add_worksheet(None)?;
+/// let mut chartsheet = workbook.add_chartsheet(None)?;
+/// # for i in 0..5 {
+/// #     worksheet.write_number(i, 0, (i*10).into(), None)?;
+/// # }
+/// let mut chart = workbook.add_chart(ChartType::Column);
+/// chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+/// chartsheet.set_chart(&chart)?;
+/// chartsheet.activate();
+/// workbook.close()
+/// # }
+/// ```
+/// Please read [original libxlsxwriter document](https://libxlsxwriter.github.io/chartsheet_8h.html) for description missing functions.
+pub struct Chartsheet<'a> {
+    pub(crate) _workbook: &'a Workbook,
+    pub(crate) chartsheet: *mut libxlsxwriter_sys::lxw_chartsheet,
+}
+
+impl<'a> Chartsheet<'a> {
+    /// This function is used to insert a chart object into a chartsheet. The chart is then displayed as a full page.
+    ///
+    /// A chart can only be inserted once into a chartsheet or a worksheet, and only into a sheet of the workbook that created it.
+    pub fn set_chart(&mut self, chart: &Chart) -> Result<(), XlsxError> {
+        chart.check_workbook(self._workbook)?;
+        unsafe {
+            let result = libxlsxwriter_sys::chartsheet_set_chart(self.chartsheet, chart.chart);
+            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                Ok(())
+            } else {
+                Err(XlsxError::new(result))
+            }
+        }
+    }
+
+    /// This function is used to specify the chartsheet that will be displayed when the workbook is opened.
+    pub fn activate(&mut self) {
+        unsafe {
+            libxlsxwriter_sys::chartsheet_activate(self.chartsheet);
+        }
+    }
+
+    pub fn set_tab_color(&mut self, color: FormatColor) {
+        unsafe {
+            libxlsxwriter_sys::chartsheet_set_tab_color(self.chartsheet, color.value());
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use std::os::raw::c_char;
 use std::{ffi::CString, ptr::null_mut};
 
-use crate::{convert_bool, Format, FormatColor};
+use crate::{
+    convert_bool, option_to_c_string, Format, FormatColor, WorksheetCol, WorksheetRow, XlsxError,
+};
 
 #[derive(Debug)]
 pub enum ConditionalType {
@@ -107,7 +108,12 @@ pub enum ConditionalIconType {
 #[derive(Debug)]
 pub struct ConditionalFormat {
     pub _internal_format: libxlsxwriter_sys::lxw_conditional_format,
-    string_value: Option<Vec<u8>>,
+    string_value: Option<String>,
+    min_value_string: Option<String>,
+    mid_value_string: Option<String>,
+    max_value_string: Option<String>,
+    multi_range: Option<String>,
+    c_strings: Vec<CString>,
 }
 
 impl ConditionalType {
@@ -271,13 +277,130 @@ impl ConditionalIconType {
 
 impl ConditionalFormat {
     pub fn new(format: Format) -> Self {
+        Self::with_format_ptr(format.format)
+    }
+
+    /// Create a conditional format of the given type without a cell format. This is intended for rules such as data bars, color scales and icon sets which don't apply a [`Format`] to the cells. A format can still be added later with [`ConditionalFormat::set_format()`].
+    pub fn new_typed(conditional_type: ConditionalType) -> Self {
+        Self::with_format_ptr(null_mut()).set_conditional_type(conditional_type)
+    }
+
+    /// Create a three color scale conditional format. The minimum, 50th percentile and maximum values of the range are mapped to `min_color`, `mid_color` and `max_color`. The rule types, values and colors can still be changed with the `set_min_*`, `set_mid_*` and `set_max_*` functions.
+    pub fn three_color_scale(
+        min_color: FormatColor,
+        mid_color: FormatColor,
+        max_color: FormatColor,
+    ) -> Self {
+        Self::with_format_ptr(null_mut())
+            .set_conditional_type(ConditionalType::ThreeColorScale)
+            .set_min_rule_type(ConditionalRuleType::Minimum)
+            .set_min_color(min_color)
+            .set_mid_rule_type(ConditionalRuleType::Percentile)
+            .set_mid_value(50.)
+            .set_mid_color(mid_color)
+            .set_max_rule_type(ConditionalRuleType::Maximum)
+            .set_max_color(max_color)
+    }
+
+    /// Create a two color scale conditional format. The minimum and maximum values of the range are mapped to `min_color` and `max_color`.
+    pub fn two_color_scale(min_color: FormatColor, max_color: FormatColor) -> Self {
+        Self::with_format_ptr(null_mut())
+            .set_conditional_type(ConditionalType::TwoColorScale)
+            .set_min_rule_type(ConditionalRuleType::Minimum)
+            .set_min_color(min_color)
+            .set_max_rule_type(ConditionalRuleType::Maximum)
+            .set_max_color(max_color)
+    }
+
+    /// Create a data bar conditional format with the given bar color. The bar isn't solid, the bar direction follows the worksheet context and the axis position is automatic. The negative bar, border and axis colors are left unset so that Excel uses its defaults. Excel 2010 style data bars are enabled so that negative values are drawn to the left of the axis.
+    pub fn data_bar(bar_color: FormatColor) -> Self {
+        let mut conditional_format = Self::with_format_ptr(null_mut())
+            .set_conditional_type(ConditionalType::DataBar)
+            .set_bar_color(bar_color)
+            .set_bar_solid(false)
+            .set_bar_direction(ConditionalBarDirection::Context)
+            .set_bar_axis_position(ConditionalBarAxisPosition::Automatic)
+            .set_data_bar_2010(true);
+        conditional_format._internal_format.bar_negative_color = 0;
+        conditional_format._internal_format.bar_border_color = 0;
+        conditional_format
+            ._internal_format
+            .bar_negative_border_color = 0;
+        conditional_format._internal_format.bar_axis_color = 0;
+        conditional_format
+    }
+
+    /// Create a solid data bar conditional format with a border. Negative values are drawn with `negative_color` as both fill and border color. The other defaults are the same as [`ConditionalFormat::data_bar()`].
+    pub fn data_bar_full(
+        bar_color: FormatColor,
+        negative_color: FormatColor,
+        border_color: FormatColor,
+    ) -> Self {
+        Self::data_bar(bar_color)
+            .set_bar_solid(true)
+            .set_bar_no_border(false)
+            .set_bar_border_color(border_color)
+            .set_bar_negative_color(negative_color)
+            .set_bar_negative_border_color(negative_color)
+    }
+
+    /// Create a conditional format matching the `n` highest values of the range. Use [`ConditionalFormat::set_format()`] to set the format applied to the matching cells.
+    pub fn top_n(n: u16) -> Self {
+        Self::top_or_bottom(ConditionalType::Top, n, false)
+    }
+
+    /// Create a conditional format matching the `n` lowest values of the range. Use [`ConditionalFormat::set_format()`] to set the format applied to the matching cells.
+    pub fn bottom_n(n: u16) -> Self {
+        Self::top_or_bottom(ConditionalType::Bottom, n, false)
+    }
+
+    /// Create a conditional format matching the highest `percent`% values of the range. Use [`ConditionalFormat::set_format()`] to set the format applied to the matching cells.
+    pub fn top_percent(percent: u16) -> Self {
+        Self::top_or_bottom(ConditionalType::Top, percent, true)
+    }
+
+    /// Create a conditional format matching the lowest `percent`% values of the range. Use [`ConditionalFormat::set_format()`] to set the format applied to the matching cells.
+    pub fn bottom_percent(percent: u16) -> Self {
+        Self::top_or_bottom(ConditionalType::Bottom, percent, true)
+    }
+
+    fn top_or_bottom(conditional_type: ConditionalType, value: u16, percent: bool) -> Self {
+        let mut conditional_format = Self::with_format_ptr(null_mut())
+            .set_conditional_type(conditional_type)
+            .set_value(value.into());
+        conditional_format._internal_format.criteria = if percent {
+            ConditionalCriteria::TopOrBottomPercent.value()
+        } else {
+            libxlsxwriter_sys::lxw_conditional_criteria_LXW_CONDITIONAL_CRITERIA_NONE as u8
+        };
+        conditional_format
+    }
+
+    /// Create an icon set conditional format. The number of icons is given by the style, e.g. `ThreeTrafficLightsRimmed` has 3 icons and `FiveRatings` has 5 icons, and the range is split into that many equal percentile bands.
+    pub fn icon_set(style: ConditionalIconType) -> Self {
+        Self::with_format_ptr(null_mut())
+            .set_conditional_type(ConditionalType::IconSets)
+            .set_icon_style(style)
+    }
+
+    /// Reverse the order of the icons of an icon set.
+    pub fn reversed(self) -> Self {
+        self.set_reverse_icons(true)
+    }
+
+    /// Show only the icons of an icon set and hide the cell values.
+    pub fn icons_only(self) -> Self {
+        self.set_icons_only(true)
+    }
+
+    fn with_format_ptr(format: *mut libxlsxwriter_sys::lxw_format) -> Self {
         let internal_format = libxlsxwriter_sys::lxw_conditional_format {
             type_: libxlsxwriter_sys::lxw_conditional_format_types_LXW_CONDITIONAL_TYPE_CELL as u8,
             criteria: libxlsxwriter_sys::lxw_conditional_criteria_LXW_CONDITIONAL_CRITERIA_EQUAL_TO
                 as u8,
             value: 0.0,
             value_string: null_mut(),
-            format: format.format,
+            format,
             min_value: 0.0,
             min_value_string: null_mut(),
             min_rule_type: libxlsxwriter_sys::lxw_conditional_format_rule_types_LXW_CONDITIONAL_RULE_TYPE_NUMBER as u8,
@@ -313,6 +436,11 @@ impl ConditionalFormat {
         ConditionalFormat {
             _internal_format: internal_format,
             string_value: None,
+            min_value_string: None,
+            mid_value_string: None,
+            max_value_string: None,
+            multi_range: None,
+            c_strings: Vec::new(),
         }
     }
 
@@ -332,12 +460,7 @@ impl ConditionalFormat {
     }
 
     pub fn set_value_string(mut self, value_string: Option<String>) -> Self {
-        self.string_value = option_str_to_cstr_bytes(&value_string);
-        self._internal_format.value_string =
-            self.string_value
-                .as_mut()
-                .map(|x| x.as_mut_ptr())
-                .unwrap_or(std::ptr::null_mut()) as *mut c_char;
+        self.string_value = value_string;
         self
     }
 
@@ -352,11 +475,7 @@ impl ConditionalFormat {
     }
 
     pub fn set_min_value_string(mut self, min_value_string: Option<String>) -> Self {
-        self._internal_format.min_value_string = option_str_to_cstr_bytes(&min_value_string)
-            .as_mut()
-            .map(|x| x.as_mut_ptr())
-            .unwrap_or(std::ptr::null_mut())
-            as *mut c_char;
+        self.min_value_string = min_value_string;
         self
     }
 
@@ -376,11 +495,7 @@ impl ConditionalFormat {
     }
 
     pub fn set_mid_value_string(mut self, mid_value_string: Option<String>) -> Self {
-        self._internal_format.mid_value_string = option_str_to_cstr_bytes(&mid_value_string)
-            .as_mut()
-            .map(|x| x.as_mut_ptr())
-            .unwrap_or(std::ptr::null_mut())
-            as *mut c_char;
+        self.mid_value_string = mid_value_string;
         self
     }
 
@@ -400,11 +515,7 @@ impl ConditionalFormat {
     }
 
     pub fn set_max_value_string(mut self, max_value_string: Option<String>) -> Self {
-        self._internal_format.max_value_string = option_str_to_cstr_bytes(&max_value_string)
-            .as_mut()
-            .map(|x| x.as_mut_ptr())
-            .unwrap_or(std::ptr::null_mut())
-            as *mut c_char;
+        self.max_value_string = max_value_string;
         self
     }
 
@@ -503,12 +614,49 @@ impl ConditionalFormat {
     }
 
     pub fn set_multi_range(mut self, multi_range: Option<String>) -> Self {
-        self._internal_format.multi_range = option_str_to_cstr_bytes(&multi_range)
-            .as_mut()
-            .map(|x| x.as_mut_ptr())
-            .unwrap_or(std::ptr::null_mut())
-            as *mut c_char;
-        self
+        self.multi_range = multi_range;
+        self
+    }
+
+    /// Apply the rule to several ranges given as `(first_row, first_col, last_row, last_col)`. This builds the range string of
+    /// `set_multi_range()`, a range of a single cell is written as that cell. A row that has no A1 style name, such as
+    /// `u32::MAX`, returns an error.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-conditional_format_multi_range_cells-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let mut conditional_format = ConditionalFormat::new(workbook.add_format().set_bold())
+    ///     .set_conditional_type(ConditionalType::Cell)
+    ///     .set_criteria(ConditionalCriteria::GreaterThan)
+    ///     .set_value(5.)
+    ///     .set_stop_if_true(true)
+    ///     // "A1:A5 C1:C5"
+    ///     .set_multi_range_cells(&[(0, 0, 4, 0), (0, 2, 4, 2)])?;
+    /// // The range passed here is the first of the ranges.
+    /// worksheet.conditional_format_range(0, 0, 4, 0, &mut conditional_format)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_multi_range_cells(
+        self,
+        ranges: &[(WorksheetRow, WorksheetCol, WorksheetRow, WorksheetCol)],
+    ) -> Result<Self, XlsxError> {
+        let multi_range = ranges
+            .iter()
+            .map(|&(first_row, first_col, last_row, last_col)| {
+                if (first_row, first_col) == (last_row, last_col) {
+                    cell_name(first_row, first_col)
+                } else {
+                    Ok(format!(
+                        "{}:{}",
+                        cell_name(first_row, first_col)?,
+                        cell_name(last_row, last_col)?
+                    ))
+                }
+            })
+            .collect::<Result<Vec<String>, XlsxError>>()?;
+        Ok(self.set_multi_range(Some(multi_range.join(" "))))
     }
 
     pub fn set_stop_if_true(mut self, stop_if_true: bool) -> Self {
@@ -517,11 +665,42 @@ impl ConditionalFormat {
     }
 }
 
-fn option_str_to_cstr_bytes(s: &Option<String>) -> Option<Vec<u8>> {
-    s.as_ref().map(|x| {
-        CString::new(x as &str)
-            .unwrap()
-            .into_bytes_with_nul()
-            .to_vec()
-    })
+impl ConditionalFormat {
+    /// Point the string fields of `_internal_format` to NUL terminated copies of the strings set with the setters, kept alive
+    /// by `self`. Called before the rule is added to a worksheet, so that a string with a NUL character returns an error there.
+    pub(crate) fn update_c_strings(&mut self) -> Result<(), XlsxError> {
+        let mut c_strings = Vec::new();
+        self._internal_format.value_string =
+            option_to_c_string(self.string_value.as_deref(), &mut c_strings)?;
+        self._internal_format.min_value_string =
+            option_to_c_string(self.min_value_string.as_deref(), &mut c_strings)?;
+        self._internal_format.mid_value_string =
+            option_to_c_string(self.mid_value_string.as_deref(), &mut c_strings)?;
+        self._internal_format.max_value_string =
+            option_to_c_string(self.max_value_string.as_deref(), &mut c_strings)?;
+        self._internal_format.multi_range =
+            option_to_c_string(self.multi_range.as_deref(), &mut c_strings)?;
+        self.c_strings = c_strings;
+        Ok(())
+    }
+}
+
+/// A1 style name of a zero indexed cell.
+pub(crate) fn cell_name(row: WorksheetRow, col: WorksheetCol) -> Result<String, XlsxError> {
+    let row_number = row.checked_add(1).ok_or_else(|| {
+        XlsxError::new(libxlsxwriter_sys::lxw_error_LXW_ERROR_WORKSHEET_INDEX_OUT_OF_RANGE)
+    })?;
+    let mut letters = Vec::new();
+    let mut col = u32::from(col) + 1;
+    while col > 0 {
+        col -= 1;
+        letters.push(b'A' + (col % 26) as u8);
+        col /= 26;
+    }
+    letters.reverse();
+    Ok(format!(
+        "{}{}",
+        String::from_utf8(letters).unwrap(),
+        row_number
+    ))
 }
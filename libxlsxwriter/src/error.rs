@@ -4,22 +4,82 @@ use std::fmt::{self, Display};
 
 pub const UNKNOWN_ERROR_CODE: libxlsxwriter_sys::lxw_error = 1000;
 pub(crate) const NUMBER_OF_COLUMNS_IS_NOT_MATCHED: libxlsxwriter_sys::lxw_error = 1001;
+pub(crate) const EMPTY_FORMULA: libxlsxwriter_sys::lxw_error = 1002;
+pub(crate) const STRING_CONTAINS_NUL: libxlsxwriter_sys::lxw_error = 1003;
+pub(crate) const TABLE_STYLE_NUMBER_OUT_OF_RANGE: libxlsxwriter_sys::lxw_error = 1004;
+pub(crate) const REVERSED_RANGE: libxlsxwriter_sys::lxw_error = 1005;
+pub(crate) const SCALE_OUT_OF_RANGE: libxlsxwriter_sys::lxw_error = 1006;
+pub(crate) const DATETIME_OUT_OF_RANGE: libxlsxwriter_sys::lxw_error = 1007;
+pub(crate) const CHART_FROM_OTHER_WORKBOOK: libxlsxwriter_sys::lxw_error = 1008;
+pub(crate) const UNKNOWN_STYLE: libxlsxwriter_sys::lxw_error = 1009;
+pub(crate) const NON_FINITE_NUMBER: libxlsxwriter_sys::lxw_error = 1010;
+pub(crate) const INVALID_RICH_STRING: libxlsxwriter_sys::lxw_error = 1011;
+pub(crate) const TABLE_TOTAL_CONFLICT: libxlsxwriter_sys::lxw_error = 1012;
+pub(crate) const URL_UNKNOWN_SCHEME: libxlsxwriter_sys::lxw_error = 1013;
+pub(crate) const URL_MISSING_SHEET: libxlsxwriter_sys::lxw_error = 1014;
+pub(crate) const URL_MISSING_TARGET: libxlsxwriter_sys::lxw_error = 1015;
+pub(crate) const INVALID_HEX_COLOR: libxlsxwriter_sys::lxw_error = 1016;
+pub(crate) const INVALID_DATETIME: libxlsxwriter_sys::lxw_error = 1017;
+pub(crate) const IMAGE_FILE_NOT_READABLE: libxlsxwriter_sys::lxw_error = 1018;
 
 #[derive(Debug)]
 pub struct XlsxError {
     pub(crate) error: libxlsxwriter_sys::lxw_error,
+    pub(crate) cell: Option<(libxlsxwriter_sys::lxw_row_t, libxlsxwriter_sys::lxw_col_t)>,
+    pub(crate) fragment: Option<usize>,
 }
 
 impl Error for XlsxError {}
 
 impl XlsxError {
     pub fn new(error: libxlsxwriter_sys::lxw_error) -> XlsxError {
-        XlsxError { error }
+        XlsxError {
+            error,
+            cell: None,
+            fragment: None,
+        }
+    }
+
+    /// The `(row, column)` of the cell that caused the error, if the error was raised while writing a range of cells.
+    pub fn cell(&self) -> Option<(libxlsxwriter_sys::lxw_row_t, libxlsxwriter_sys::lxw_col_t)> {
+        self.cell
+    }
+
+    pub(crate) fn at_cell(
+        mut self,
+        row: libxlsxwriter_sys::lxw_row_t,
+        col: libxlsxwriter_sys::lxw_col_t,
+    ) -> XlsxError {
+        self.cell = Some((row, col));
+        self
+    }
+
+    /// The zero based index of the rich string fragment that caused the error, if the error was raised by `write_rich_string()`.
+    pub fn fragment(&self) -> Option<usize> {
+        self.fragment
+    }
+
+    pub(crate) fn at_fragment(mut self, fragment: usize) -> XlsxError {
+        self.fragment = Some(fragment);
+        self
     }
 }
 
 impl Display for XlsxError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_message(f)?;
+        if let Some((row, col)) = self.cell {
+            write!(f, " (row {}, column {})", row, col)?;
+        }
+        if let Some(fragment) = self.fragment {
+            write!(f, " (fragment {})", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+impl XlsxError {
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.error {
             UNKNOWN_ERROR_CODE => {
                 write!(f, "Unknown Error")
@@ -30,6 +90,72 @@ impl Display for XlsxError {
                     "Number of columns in an option is not equal to table size"
                 )
             }
+            EMPTY_FORMULA => {
+                write!(f, "Formula is empty")
+            }
+            STRING_CONTAINS_NUL => {
+                write!(f, "String contains a NUL character")
+            }
+            TABLE_STYLE_NUMBER_OUT_OF_RANGE => {
+                write!(f, "Table style number is out of range for the style type")
+            }
+            REVERSED_RANGE => {
+                write!(f, "First row or column of the range is after the last one")
+            }
+            SCALE_OUT_OF_RANGE => {
+                write!(f, "Scale must be between 10 and 400")
+            }
+            DATETIME_OUT_OF_RANGE => {
+                write!(f, "Year of the date is out of range")
+            }
+            CHART_FROM_OTHER_WORKBOOK => {
+                write!(f, "Chart was created by another workbook")
+            }
+            UNKNOWN_STYLE => {
+                write!(f, "Style name is not defined")
+            }
+            NON_FINITE_NUMBER => {
+                write!(f, "Excel doesn't support NaN or infinite numbers")
+            }
+            INVALID_RICH_STRING => {
+                write!(f, "Rich string is empty or contains an empty fragment")
+            }
+            TABLE_TOTAL_CONFLICT => {
+                write!(
+                    f,
+                    "Table column has both a total string and a total function"
+                )
+            }
+            URL_UNKNOWN_SCHEME => {
+                write!(
+                    f,
+                    "URL has no scheme, use e.g. https://, mailto:, internal: or external:"
+                )
+            }
+            URL_MISSING_SHEET => {
+                write!(
+                    f,
+                    "Internal link has no sheet name, use e.g. internal:Sheet1!A1"
+                )
+            }
+            URL_MISSING_TARGET => {
+                write!(
+                    f,
+                    "URL has no target after the scheme or location separator"
+                )
+            }
+            INVALID_HEX_COLOR => {
+                write!(f, "Color must be 6 hex digits in the form #RRGGBB")
+            }
+            INVALID_DATETIME => {
+                write!(
+                    f,
+                    "Month, day, hour, minute or second of the date is out of range"
+                )
+            }
+            IMAGE_FILE_NOT_READABLE => {
+                write!(f, "Image file doesn't exist or can't be read")
+            }
             _ => unsafe {
                 match ffi::CStr::from_ptr(libxlsxwriter_sys::lxw_strerror(self.error)).to_str() {
                     Ok(error_text) => write!(f, "{}", error_text),
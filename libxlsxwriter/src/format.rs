@@ -1,5 +1,4 @@
-use super::Workbook;
-use std::ffi::CString;
+use super::{error, to_c_string, Workbook, XlsxError};
 
 #[allow(clippy::unreadable_literal)]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -20,11 +19,53 @@ pub enum FormatColor {
     Silver,
     White,
     Yellow,
+    /// An arbitrary RGB color in `0xRRGGBB` format, e.g. `FormatColor::Custom(0x1F497D)`.
     Custom(u32),
 }
 
 #[allow(clippy::unreadable_literal)]
 impl FormatColor {
+    /// Create a custom color from a packed `0xRRGGBB` value. Bits above the 24 bit RGB value are ignored.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// assert_eq!(FormatColor::rgb(0x1A2B3C), FormatColor::Custom(0x1A2B3C));
+    /// assert_eq!(FormatColor::rgb(0x1A2B3C).value(), 0x1A2B3C);
+    /// ```
+    pub fn rgb(rgb: u32) -> FormatColor {
+        FormatColor::Custom(rgb & 0xFFFFFF)
+    }
+
+    /// Parse a custom color from a `#RRGGBB` hex string. The `#` is optional and the digits are case insensitive.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// assert_eq!(FormatColor::from_hex("#1A2B3C")?, FormatColor::Custom(0x1A2B3C));
+    /// assert_eq!(FormatColor::from_hex("ff6600")?, FormatColor::Custom(0xFF6600));
+    /// assert!(FormatColor::from_hex("#12345").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<FormatColor, XlsxError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if digits.len() != 6 || !digits.bytes().all(|x| x.is_ascii_hexdigit()) {
+            return Err(XlsxError::new(error::INVALID_HEX_COLOR));
+        }
+        let rgb = u32::from_str_radix(digits, 16)
+            .map_err(|_| XlsxError::new(error::INVALID_HEX_COLOR))?;
+        Ok(FormatColor::Custom(rgb))
+    }
+
+    /// Create a custom color from a packed `0xRRGGBB` value. Unlike [`FormatColor::rgb()`], a value above `0xFFFFFF` returns an error.
+    pub fn from_hex_u32(rgb: u32) -> Result<FormatColor, XlsxError> {
+        if rgb > 0xFFFFFF {
+            Err(XlsxError::new(error::INVALID_HEX_COLOR))
+        } else {
+            Ok(FormatColor::Custom(rgb))
+        }
+    }
+
+    /// Return the packed color value passed to libxlsxwriter. Custom colors are passed unchanged, except for `0x000000` which
+    /// libxlsxwriter treats as "no color" and is mapped to the same value as [`FormatColor::Black`].
     pub fn value(self) -> u32 {
         match self {
             FormatColor::Black => 0x1000000,
@@ -43,6 +84,7 @@ impl FormatColor {
             FormatColor::Silver => 0xC0C0C0,
             FormatColor::White => 0xFFFFFF,
             FormatColor::Yellow => 0xFFFF00,
+            FormatColor::Custom(0) => FormatColor::Black.value(),
             FormatColor::Custom(x) => x,
         }
     }
@@ -76,6 +118,7 @@ impl FormatUnderline {
     }
 }
 
+/// Font script used by [`Format::set_font_script()`].
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FormatScript {
     SuperScript,
@@ -282,14 +325,25 @@ pub struct Format<'a> {
 }
 
 impl<'a> Format<'a> {
-    pub fn set_font_name(self, font_name: &str) -> Self {
+    /// Set the font used in the cell. A font name with an interior NUL character returns an error, so the setter is followed by `?`:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-format-set_font_name-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let format = workbook.add_format()
+    ///     .set_font_name("Avenir Black Oblique")?
+    ///     .set_font_size(12.);
+    /// worksheet.write_string(0, 0, "Avenir Black Oblique", Some(&format))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_font_name(self, font_name: &str) -> Result<Self, XlsxError> {
+        let font_name = to_c_string(font_name)?;
         unsafe {
-            libxlsxwriter_sys::format_set_font_name(
-                self.format,
-                CString::new(font_name).unwrap().as_c_str().as_ptr(),
-            );
+            libxlsxwriter_sys::format_set_font_name(self.format, font_name.as_ptr());
         }
-        self
+        Ok(self)
     }
 
     pub fn set_font_size(self, font_size: f64) -> Self {
@@ -327,6 +381,19 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Draw a line through the text, e.g. to mark deprecated rows:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-format-set_font_strikeout-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let deprecated = workbook.add_format()
+    ///     .set_font_strikeout()
+    ///     .set_font_color(FormatColor::Gray);
+    /// worksheet.write_string(0, 0, "Old product", Some(&deprecated))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
     pub fn set_font_strikeout(self) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_font_strikeout(self.format);
@@ -334,6 +401,37 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Draw the font as an outline. This is a Mac only font style and isn't displayed by Excel on Windows.
+    pub fn set_font_outline(self) -> Self {
+        unsafe {
+            libxlsxwriter_sys::format_set_font_outline(self.format);
+        }
+        self
+    }
+
+    /// Draw the font with a shadow. This is a Mac only font style and isn't displayed by Excel on Windows.
+    pub fn set_font_shadow(self) -> Self {
+        unsafe {
+            libxlsxwriter_sys::format_set_font_shadow(self.format);
+        }
+        self
+    }
+
+    /// Set the font script to superscript or subscript. This is mostly useful for fragments of a rich string:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-format-set_font_script-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let superscript = workbook.add_format()
+    ///     .set_font_script(FormatScript::SuperScript);
+    /// let subscript = workbook.add_format()
+    ///     .set_font_script(FormatScript::SubScript);
+    /// worksheet.write_rich_string(0, 0, &[("x", None), ("2", Some(&superscript))], None)?;
+    /// worksheet.write_rich_string(1, 0, &[("H", None), ("2", Some(&subscript)), ("O", None)], None)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
     pub fn set_font_script(self, script: FormatScript) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_font_script(self.format, script.value());
@@ -341,30 +439,89 @@ impl<'a> Format<'a> {
         self
     }
 
-    pub fn set_num_format(self, num_font: &str) -> Self {
+    /// Set the number format of the cell, e.g. `"0.00"` or `"yyyy-mm-dd"`. Like [`Format::set_font_name()`], a format string with an
+    /// interior NUL character returns an error.
+    pub fn set_num_format(self, num_font: &str) -> Result<Self, XlsxError> {
+        let num_font = to_c_string(num_font)?;
         unsafe {
-            libxlsxwriter_sys::format_set_num_format(
-                self.format,
-                CString::new(num_font).unwrap().as_c_str().as_ptr(),
-            );
+            libxlsxwriter_sys::format_set_num_format(self.format, num_font.as_ptr());
+        }
+        Ok(self)
+    }
+
+    /// Unlock the cell. Cells are locked by default, but locking only has an effect once the worksheet is protected with
+    /// [`Worksheet::protect()`](struct.Worksheet.html#method.protect). The common pattern is to protect a sheet and unlock the input cells:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-format-set_unlocked-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let input = workbook.add_format()
+    ///     .set_unlocked();
+    /// let formula = workbook.add_format()
+    ///     .set_hidden();
+    /// worksheet.write_number(0, 0, 42., Some(&input))?;
+    /// worksheet.write_formula(0, 1, "=A1*2", Some(&formula))?;
+    /// worksheet.protect("", &Protection::new())?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn set_unlocked(self) -> Self {
+        unsafe {
+            libxlsxwriter_sys::format_set_unlocked(self.format);
         }
         self
     }
 
-    pub fn set_font_unlocked(self) -> Self {
+    /// Lock the cell again after [`Format::set_unlocked()`]. Cells are locked by default, so this is only needed to undo an unlock.
+    pub fn set_locked(self) -> Self {
+        // libxlsxwriter has no format_set_locked(), format_set_unlocked() just clears this flag.
         unsafe {
-            libxlsxwriter_sys::format_set_unlocked(self.format);
+            (*self.format).locked = 1;
         }
         self
     }
 
-    pub fn set_font_hidden(self) -> Self {
+    /// Hide the formula of the cell in the formula bar. Like locking, this only has an effect once the worksheet is protected.
+    /// See [`Format::set_unlocked()`] for an example.
+    pub fn set_hidden(self) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_hidden(self.format);
         }
         self
     }
 
+    #[deprecated(
+        note = "use `set_unlocked()` instead, the setting applies to the cell and not the font"
+    )]
+    pub fn set_font_unlocked(self) -> Self {
+        self.set_unlocked()
+    }
+
+    #[deprecated(
+        note = "use `set_hidden()` instead, the setting applies to the cell and not the font"
+    )]
+    pub fn set_font_hidden(self) -> Self {
+        self.set_hidden()
+    }
+
+    /// Set the horizontal or vertical alignment of the cell contents. Horizontal and vertical alignments are stored separately,
+    /// so call this function once for each direction to combine them:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-format-set_align-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let header = workbook.add_format()
+    ///     .set_bold()
+    ///     .set_align(FormatAlignment::Center)
+    ///     .set_align(FormatAlignment::VerticalCenter);
+    /// worksheet.set_row(0, 30., Some(&header))?;
+    /// worksheet.write_string(0, 0, "Header", Some(&header))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// `FormatAlignment::CenterAcross` centers the text across the adjacent blank cells that have the same format, which is an alternative to merging the cells.
     pub fn set_align(self, align: FormatAlignment) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_align(self.format, align.value());
@@ -372,6 +529,22 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Wrap the text in the cell. Newlines in the string also start a new line in a wrapped cell:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-format-set_text_wrap-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let wrap = workbook.add_format()
+    ///     .set_text_wrap();
+    /// let header = workbook.add_format()
+    ///     .set_bold()
+    ///     .set_rotation(45);
+    /// worksheet.write_string(0, 0, "Rotated header", Some(&header))?;
+    /// worksheet.write_string(1, 0, "Some long text\nthat is wrapped", Some(&wrap))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
     pub fn set_text_wrap(self) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_text_wrap(self.format);
@@ -379,6 +552,8 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Rotate the text by `angle` degrees. Angles from -90 to 90 are supported, as well as 270 which stacks the letters
+    /// vertically from top to bottom. libxlsxwriter ignores any other angle. See [`Format::set_text_wrap()`] for an example.
     pub fn set_rotation(self, angle: i16) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_rotation(self.format, angle);
@@ -386,6 +561,8 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Indent the cell contents by `level` steps. Each step is about the width of one character. Indentation applies to
+    /// left or right aligned text and works best together with [`Format::set_align()`].
     pub fn set_indent(self, level: u8) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_indent(self.format, level);
@@ -393,6 +570,7 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Shrink the text so that it fits in the column width. This has no effect on wrapped text.
     pub fn set_shrink(self) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_shrink(self.format);
@@ -400,6 +578,7 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Set the fill pattern of the cell. The pattern is drawn with the foreground color on top of the background color.
     pub fn set_pattern(self, pattern: FormatPatterns) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_pattern(self.format, pattern.value());
@@ -407,6 +586,22 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Set the background color of the cell. If no pattern is set a solid fill is used:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-format-set_bg_color-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let highlight = workbook.add_format()
+    ///     .set_pattern(FormatPatterns::Solid)
+    ///     .set_bg_color(FormatColor::Yellow);
+    /// worksheet.write_string(0, 0, "Highlighted", Some(&highlight))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// ### Note
+    /// Excel draws a solid fill with the *foreground* color. libxlsxwriter swaps the colors when a solid pattern only has a background
+    /// color, so the example above works as expected. When both colors are set, [`Format::set_fg_color()`] is the fill color of a solid pattern.
     pub fn set_bg_color(self, color: FormatColor) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_bg_color(self.format, color.value());
@@ -414,6 +609,7 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Set the foreground color of the fill pattern. See [`Format::set_bg_color()`].
     pub fn set_fg_color(self, color: FormatColor) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_fg_color(self.format, color.value());
@@ -421,6 +617,20 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Set the border style of all four sides of the cell. Use the `set_border_bottom()`, `set_border_top()`, `set_border_left()` and
+    /// `set_border_right()` functions to set the sides separately:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-format-set_border-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let boxed = workbook.add_format()
+    ///     .set_border(FormatBorder::Thin)
+    ///     .set_border_color(FormatColor::Navy);
+    /// worksheet.write_string(1, 1, "Boxed", Some(&boxed))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
     pub fn set_border(self, border: FormatBorder) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_border(self.format, border.value());
@@ -456,6 +666,7 @@ impl<'a> Format<'a> {
         self
     }
 
+    /// Set the color of all four borders of the cell. A border style must also be set for the color to be visible.
     pub fn set_border_color(self, color: FormatColor) -> Self {
         unsafe {
             libxlsxwriter_sys::format_set_border_color(self.format, color.value());
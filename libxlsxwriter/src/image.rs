@@ -0,0 +1,122 @@
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Natural size of an image in pixels, returned by [`Worksheet::insert_image()`](crate::Worksheet::insert_image).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageDimensions {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// How [`Worksheet::insert_image_fit_to_range()`](crate::Worksheet::insert_image_fit_to_range) scales an image to a range of cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FitMode {
+    /// Scale the width and the height separately so that the image fills the range exactly. The aspect ratio isn't kept.
+    Stretch,
+    /// Keep the aspect ratio and scale the image to the largest size that fits inside the range. The image is centered, so there is
+    /// empty space on two sides of it unless the range has the same aspect ratio.
+    Contain,
+    /// Keep the aspect ratio and scale the image to the smallest size that covers the whole range. libxlsxwriter can't crop images,
+    /// so the part that doesn't fit isn't cut off, it extends past the right or the bottom edge of the range.
+    Cover,
+}
+
+/// Number of bytes at the start of a PNG, GIF or BMP file that hold its size.
+const HEADER_LEN: u64 = 26;
+
+/// Return the `(width, height)` in pixels of the PNG, JPEG, GIF or BMP image file at `path`, or `None` if the format isn't
+/// recognized. libxlsxwriter reads the dimensions when an image is inserted but doesn't expose them. Only the header is read.
+pub(crate) fn image_file_dimensions(path: impl AsRef<Path>) -> io::Result<Option<(u32, u32)>> {
+    read_image_dimensions(BufReader::new(File::open(path)?))
+}
+
+/// The same as `image_file_dimensions()` for an image read from `reader`.
+pub(crate) fn read_image_dimensions(
+    mut reader: impl Read + Seek,
+) -> io::Result<Option<(u32, u32)>> {
+    let mut header = Vec::new();
+    (&mut reader).take(HEADER_LEN).read_to_end(&mut header)?;
+    let dimensions = if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png_dimensions(&header)
+    } else if header.starts_with(&[0xff, 0xd8]) {
+        reader.seek(SeekFrom::Start(2))?;
+        return match jpeg_dimensions(&mut reader) {
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            result => result,
+        };
+    } else if header.starts_with(b"GIF8") {
+        gif_dimensions(&header)
+    } else if header.starts_with(b"BM") {
+        bmp_dimensions(&header)
+    } else {
+        None
+    };
+    Ok(dimensions)
+}
+
+fn png_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    // The IHDR chunk always comes first, right after the signature.
+    if data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((
+        u32::from_be_bytes(data.get(16..20)?.try_into().ok()?),
+        u32::from_be_bytes(data.get(20..24)?.try_into().ok()?),
+    ))
+}
+
+fn gif_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    Some((
+        u16_le(data.get(6..8)?).into(),
+        u16_le(data.get(8..10)?).into(),
+    ))
+}
+
+fn bmp_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let width = i32::from_le_bytes(data.get(18..22)?.try_into().ok()?);
+    let height = i32::from_le_bytes(data.get(22..26)?.try_into().ok()?);
+    // A negative height is a top-down bitmap.
+    Some((width.unsigned_abs(), height.unsigned_abs()))
+}
+
+/// Find the frame header of a JPEG image, `reader` being right after the start of image marker. The segments before it, such
+/// as EXIF data, can be large, so they are skipped with `seek()` instead of being read.
+fn jpeg_dimensions(reader: &mut (impl Read + Seek)) -> io::Result<Option<(u32, u32)>> {
+    let mut byte = [0u8; 1];
+    loop {
+        reader.read_exact(&mut byte)?;
+        if byte[0] != 0xff {
+            return Ok(None);
+        }
+        // Any number of 0xFF fill bytes may come before a marker.
+        while byte[0] == 0xff {
+            reader.read_exact(&mut byte)?;
+        }
+        let marker = byte[0];
+        let mut length = [0u8; 2];
+        reader.read_exact(&mut length)?;
+        let length = u16_be(&length);
+        if length < 2 {
+            return Ok(None);
+        }
+        // SOF0 to SOF15 hold the frame size, except DHT (C4), JPG (C8) and DAC (CC).
+        if (0xc0..=0xcf).contains(&marker) && ![0xc4, 0xc8, 0xcc].contains(&marker) {
+            let mut frame = [0u8; 5];
+            reader.read_exact(&mut frame)?;
+            let height = u16_be(&frame[1..3]);
+            let width = u16_be(&frame[3..5]);
+            return Ok(Some((width.into(), height.into())));
+        }
+        reader.seek(SeekFrom::Current(i64::from(length) - 2))?;
+    }
+}
+
+fn u16_le(bytes: &[u8]) -> u16 {
+    u16::from_le_bytes([bytes[0], bytes[1]])
+}
+
+fn u16_be(bytes: &[u8]) -> u16 {
+    u16::from_be_bytes([bytes[0], bytes[1]])
+}
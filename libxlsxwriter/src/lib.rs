@@ -54,7 +54,7 @@
 //! )?;
 //! sheet1.merge_range(2, 0, 3, 2, "Hello, world", Some(&format3))?;
 //!
-//! sheet1.set_selection(1, 0, 1, 2);
+//! sheet1.set_selection(1, 0, 1, 2)?;
 //! sheet1.set_tab_color(FormatColor::Cyan);
 //! workbook.close()?;
 //! # Ok(())
@@ -67,17 +67,23 @@
 extern crate libxlsxwriter_sys;
 
 mod chart;
+mod chartsheet;
 mod conditional_formatting;
 mod error;
 mod format;
+mod image;
+mod report;
 mod validation;
 mod workbook;
 mod worksheet;
 
 pub use chart::*;
+pub use chartsheet::*;
 pub use conditional_formatting::*;
 pub use error::XlsxError;
 pub use format::*;
+pub use image::{FitMode, ImageDimensions};
+pub use report::*;
 pub use validation::*;
 pub use workbook::*;
 pub use worksheet::*;
@@ -85,6 +91,7 @@ pub use worksheet::*;
 pub use libxlsxwriter_sys::lxw_conditional_format_types_LXW_CONDITIONAL_TYPE_CELL;
 
 use std::ffi::CString;
+use std::os::raw::c_char;
 
 fn convert_bool(value: bool) -> u8 {
     let result = if value {
@@ -95,8 +102,44 @@ fn convert_bool(value: bool) -> u8 {
     result as u8
 }
 
-fn convert_str(value: &str) -> Vec<u8> {
-    CString::new(value).unwrap().as_bytes_with_nul().to_vec()
+/// Convert a string for libxlsxwriter. A string with an interior NUL character can't be passed to C, so an error is returned instead of panicking.
+fn to_c_string(value: &str) -> Result<CString, XlsxError> {
+    CString::new(value).map_err(|_| XlsxError::new(error::STRING_CONTAINS_NUL))
+}
+
+/// Convert `value` with `to_c_string()` and keep the result in `strings`, which must outlive the returned pointer. `None` becomes a
+/// null pointer.
+fn option_to_c_string(
+    value: Option<&str>,
+    strings: &mut Vec<CString>,
+) -> Result<*mut c_char, XlsxError> {
+    match value {
+        Some(value) => {
+            let value = to_c_string(value)?;
+            let ptr = value.as_ptr() as *mut c_char;
+            strings.push(value);
+            Ok(ptr)
+        }
+        None => Ok(std::ptr::null_mut()),
+    }
+}
+
+/// Call `f` with a NUL terminated copy of `value`, like `to_c_string(value)?.as_ptr()`. Short strings are copied to a
+/// stack buffer instead of a `CString`, which saves a heap allocation per cell when writing many cells in a loop.
+fn with_c_str<T>(value: &str, f: impl FnOnce(*const c_char) -> T) -> Result<T, XlsxError> {
+    const STACK_LEN: usize = 128;
+    let bytes = value.as_bytes();
+    if bytes.len() < STACK_LEN {
+        if bytes.contains(&0) {
+            return Err(XlsxError::new(error::STRING_CONTAINS_NUL));
+        }
+        let mut buffer = [0u8; STACK_LEN];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        Ok(f(buffer.as_ptr() as *const c_char))
+    } else {
+        let value = to_c_string(value)?;
+        Ok(f(value.as_ptr()))
+    }
 }
 
 #[cfg(test)]
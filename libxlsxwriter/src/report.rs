@@ -0,0 +1,207 @@
+use super::{
+    error, CellValue, Format, TableColumn, TableOptions, TableStyleType, Workbook, WorksheetCol,
+    WorksheetRow, XlsxError,
+};
+use std::collections::HashMap;
+
+type StyleFn = Box<dyn for<'a> Fn(Format<'a>) -> Result<Format<'a>, XlsxError>>;
+
+/// A high level builder for simple tabular reports. Each sheet of the report holds one table with a header row.
+///
+/// Styles are registered by name and applied to table headers or columns. Nothing is written until [`ReportBuilder::finish()`] is called.
+/// ```rust
+/// use xlsxwriter::*;
+/// # fn main() -> Result<(), XlsxError> {
+/// let mut report = ReportBuilder::new("test-report_builder-1.xlsx");
+/// report
+///     .style("header", |format| Ok(format.set_bold()))
+///     .style("money", |format| format.set_num_format("$#,##0.00"));
+/// report
+///     .sheet("Sales")
+///     .table(
+///         &["Region", "Revenue"],
+///         vec![
+///             vec!["North".into(), 1250.5.into()],
+///             vec!["South".into(), 980.into()],
+///         ],
+///     )
+///     .header_style("header")
+///     .column_style(1, "money")
+///     .freeze_header();
+/// report.finish()
+/// # }
+/// ```
+/// Use the lower level [`Workbook`] and [`Worksheet`](struct.Worksheet.html) APIs for anything the builder doesn't cover.
+pub struct ReportBuilder {
+    path: String,
+    styles: Vec<(String, StyleFn)>,
+    pub(crate) sheets: Vec<ReportSheet>,
+}
+
+/// A sheet of a [`ReportBuilder`] report, created by [`ReportBuilder::sheet()`].
+pub struct ReportSheet {
+    pub(crate) name: String,
+    pub(crate) headers: Vec<String>,
+    pub(crate) rows: Vec<Vec<CellValue>>,
+    header_style: Option<String>,
+    column_styles: Vec<(WorksheetCol, String)>,
+    table_style: (TableStyleType, u8),
+    pub(crate) freeze_header: bool,
+}
+
+impl ReportBuilder {
+    /// Start a new report that will be written to `path`.
+    pub fn new(path: &str) -> ReportBuilder {
+        ReportBuilder {
+            path: path.to_string(),
+            styles: Vec::new(),
+            sheets: Vec::new(),
+        }
+    }
+
+    /// Register a named style. The closure receives a new format and returns it with the style's properties set, or the error of a
+    /// fallible setter such as [`Format::set_num_format()`], which [`ReportBuilder::finish()`] returns. Registering a name again replaces the style.
+    pub fn style<F>(&mut self, name: &str, style: F) -> &mut ReportBuilder
+    where
+        F: for<'a> Fn(Format<'a>) -> Result<Format<'a>, XlsxError> + 'static,
+    {
+        self.styles.retain(|(x, _)| x != name);
+        self.styles.push((name.to_string(), Box::new(style)));
+        self
+    }
+
+    /// Add a sheet named `name` to the report, or return the existing sheet with that name.
+    pub fn sheet(&mut self, name: &str) -> &mut ReportSheet {
+        let index = match self.sheets.iter().position(|x| x.name == name) {
+            Some(index) => index,
+            None => {
+                self.sheets.push(ReportSheet {
+                    name: name.to_string(),
+                    headers: Vec::new(),
+                    rows: Vec::new(),
+                    header_style: None,
+                    column_styles: Vec::new(),
+                    table_style: (TableStyleType::Default, 0),
+                    freeze_header: false,
+                });
+                self.sheets.len() - 1
+            }
+        };
+        &mut self.sheets[index]
+    }
+
+    /// Write the report to disk. An error is returned if a style fails, if a sheet uses a style that wasn't registered, if a row has more cells than
+    /// the table has headers, or if libxlsxwriter fails to write the file.
+    pub fn finish(self) -> Result<(), XlsxError> {
+        let workbook = Workbook::new(&self.path);
+        let mut formats = HashMap::new();
+        for (name, style) in self.styles.iter() {
+            formats.insert(name.as_str(), style(workbook.add_format())?);
+        }
+        let format = |name: &Option<&String>| -> Result<Option<&Format>, XlsxError> {
+            match name {
+                Some(name) => formats
+                    .get(name.as_str())
+                    .map(Some)
+                    .ok_or_else(|| XlsxError::new(error::UNKNOWN_STYLE)),
+                None => Ok(None),
+            }
+        };
+
+        for sheet in self.sheets.iter() {
+            let mut worksheet = workbook.add_worksheet(Some(&sheet.name))?;
+            let header_format = format(&sheet.header_style.as_ref())?;
+            let mut column_formats = vec![None; sheet.headers.len()];
+            for (col, name) in sheet.column_styles.iter() {
+                if let Some(x) = column_formats.get_mut(usize::from(*col)) {
+                    *x = format(&Some(name))?;
+                }
+            }
+
+            for (i, row) in sheet.rows.iter().enumerate() {
+                let row_num = i as WorksheetRow + 1;
+                if row.len() > sheet.headers.len() {
+                    return Err(XlsxError::new(error::NUMBER_OF_COLUMNS_IS_NOT_MATCHED)
+                        .at_cell(row_num, sheet.headers.len() as WorksheetCol));
+                }
+                for (col, value) in row.iter().enumerate() {
+                    worksheet
+                        .write(
+                            row_num,
+                            col as WorksheetCol,
+                            value.clone(),
+                            column_formats[col],
+                        )
+                        .map_err(|e| e.at_cell(row_num, col as WorksheetCol))?;
+                }
+            }
+
+            if !sheet.headers.is_empty() {
+                let columns = sheet
+                    .headers
+                    .iter()
+                    .map(|header| TableColumn {
+                        header: Some(header.clone()),
+                        header_format: header_format.map(|x| Format {
+                            _workbook: x._workbook,
+                            format: x.format,
+                        }),
+                        ..Default::default()
+                    })
+                    .collect();
+                // A table needs at least one data row below the header.
+                let last_row = sheet.rows.len().max(1) as WorksheetRow;
+                worksheet.add_table(
+                    0,
+                    0,
+                    last_row,
+                    (sheet.headers.len() - 1) as WorksheetCol,
+                    Some(TableOptions {
+                        style_type: sheet.table_style.0,
+                        style_type_number: sheet.table_style.1,
+                        columns: Some(columns),
+                        ..Default::default()
+                    }),
+                )?;
+            }
+
+            if sheet.freeze_header {
+                worksheet.freeze_panes(1, 0);
+            }
+        }
+        workbook.close()
+    }
+}
+
+impl ReportSheet {
+    /// Set the header and the data rows of the sheet's table. Rows may be shorter than the header, missing cells are left empty.
+    pub fn table(&mut self, headers: &[&str], rows: Vec<Vec<CellValue>>) -> &mut ReportSheet {
+        self.headers = headers.iter().map(|x| x.to_string()).collect();
+        self.rows = rows;
+        self
+    }
+
+    /// Apply a named style to the header row.
+    pub fn header_style(&mut self, style: &str) -> &mut ReportSheet {
+        self.header_style = Some(style.to_string());
+        self
+    }
+
+    /// Apply a named style to the data cells of column `col`.
+    pub fn column_style(&mut self, col: WorksheetCol, style: &str) -> &mut ReportSheet {
+        self.column_styles.push((col, style.to_string()));
+        self
+    }
+
+    /// Set the table style. See [`TableOptions::style_type`].
+    pub fn table_style(&mut self, style_type: TableStyleType, number: u8) -> &mut ReportSheet {
+        self.table_style = (style_type, number);
+        self
+    }
+
+    /// Freeze the header row so that it stays visible when scrolling.
+    pub fn freeze_header(&mut self) -> &mut ReportSheet {
+        self.freeze_header = true;
+        self
+    }
+}
@@ -1,5 +1,23 @@
 use super::*;
 
+/// The `<conditionalFormatting>` element of the first worksheet of the xlsx file at `path` that applies to `sqref`.
+fn read_conditional_formatting(path: &str, sqref: &str) -> String {
+    let sheet = read_xlsx_part(path, "xl/worksheets/sheet1.xml");
+    let start = sheet
+        .find(&format!(r#"<conditionalFormatting sqref="{}">"#, sqref))
+        .unwrap();
+    let end = start + sheet[start..].find("</conditionalFormatting>").unwrap();
+    sheet[start..end].to_string()
+}
+
+/// Content of the part `name` of the xlsx file at `path`, such as `xl/styles.xml`.
+fn read_xlsx_part(path: &str, name: &str) -> String {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+    let mut content = String::new();
+    std::io::Read::read_to_string(&mut archive.by_name(name).unwrap(), &mut content).unwrap();
+    content
+}
+
 #[test]
 fn test_simple1() -> Result<(), XlsxError> {
     let workbook = Workbook::new("../target/simple1.xlsx");
@@ -28,7 +46,7 @@ fn test_simple1() -> Result<(), XlsxError> {
     )?;
     sheet1.merge_range(2, 0, 3, 2, "Hello, world", Some(&format3))?;
 
-    sheet1.set_selection(1, 0, 1, 2);
+    sheet1.set_selection(1, 0, 1, 2)?;
     sheet1.set_tab_color(FormatColor::Cyan);
     workbook.close()?;
 
@@ -42,7 +60,7 @@ fn test_sample1() -> Result<(), XlsxError> {
     let format1 = workbook
         .add_format()
         .set_bold()
-        .set_font_name("Arial")
+        .set_font_name("Arial")?
         .set_font_color(FormatColor::Red)
         .set_italic()
         .set_underline(FormatUnderline::Single);
@@ -59,7 +77,7 @@ fn test_sample1() -> Result<(), XlsxError> {
 
     let format4 = workbook
         .add_format()
-        .set_num_format("mmm d yyyy hh:mm AM/PM");
+        .set_num_format("mmm d yyyy hh:mm AM/PM")?;
 
     let mut sheet = workbook.add_worksheet(None)?;
     sheet.write_string(0, 0, "Hello", Some(&format1))?;
@@ -175,6 +193,42 @@ fn test_add_table2() -> Result<(), XlsxError> {
     Ok(())
 }
 
+#[test]
+fn test_chart_add_series_range() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart-add_series_range.xlsx");
+    let mut worksheet = workbook.add_worksheet(Some("Q1 Sales"))?;
+    for i in 0..5 {
+        worksheet.write_string(i, 0, &format!("Region {}", i + 1), None)?;
+        worksheet.write_number(i, 1, (i * 10 + 2).into(), None)?;
+        worksheet.write_number(i, 2, (i * 7 + 3).into(), None)?;
+    }
+    let mut chart = workbook.add_chart(ChartType::Column);
+    chart
+        .add_series_range(Some(("Q1 Sales", 0, 0, 4, 0)), ("Q1 Sales", 0, 1, 4, 1))?
+        .set_name("Revenue")?;
+    chart
+        .add_series_range(None, ("Q1 Sales", 0, 2, 4, 2))?
+        .set_name("Costs")?;
+    worksheet.insert_chart(1, 4, &chart)?;
+    workbook.close()?;
+
+    let chart_xml = read_xlsx_part(
+        "../target/test-chart-add_series_range.xlsx",
+        "xl/charts/chart1.xml",
+    );
+    let series: Vec<&str> = chart_xml.split("<c:ser>").skip(1).collect();
+    assert_eq!(series.len(), 2);
+    assert!(series[0].contains("<c:v>Revenue</c:v>"));
+    assert!(series[0].contains("<c:cat>"));
+    assert!(series[0].contains("<c:f>'Q1 Sales'!$A$1:$A$5</c:f>"));
+    assert!(series[0].contains("<c:f>'Q1 Sales'!$B$1:$B$5</c:f>"));
+    // The second series has no categories.
+    assert!(series[1].contains("<c:v>Costs</c:v>"));
+    assert!(!series[1].contains("<c:cat>"));
+    assert!(series[1].contains("<c:f>'Q1 Sales'!$C$1:$C$5</c:f>"));
+    Ok(())
+}
+
 #[test]
 fn test_validation() -> Result<(), XlsxError> {
     let workbook = Workbook::new("test-worksheet_validation-cell-1.xlsx");
@@ -198,3 +252,2614 @@ fn test_validation() -> Result<(), XlsxError> {
     workbook.close()?;
     Ok(())
 }
+
+#[test]
+fn test_workbook_closed_on_drop() -> Result<(), XlsxError> {
+    let path = "../target/test-workbook-drop.xlsx";
+    let _ = std::fs::remove_file(path);
+    {
+        let workbook = Workbook::new(path);
+        let mut worksheet = workbook.add_worksheet(None)?;
+        worksheet.write_string(0, 0, "Not closed explicitly", None)?;
+    }
+    let metadata = std::fs::metadata(path).expect("workbook should be written on drop");
+    assert!(metadata.len() > 0);
+    Ok(())
+}
+
+#[test]
+fn test_write_url_default_format() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_write_url-default_format.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let format_count = |workbook: &Workbook| unsafe {
+        let mut count = 0;
+        let mut format = (*(*workbook.workbook).formats).stqh_first;
+        while !format.is_null() {
+            count += 1;
+            format = (*format).list_pointers.stqe_next;
+        }
+        count
+    };
+    let formats_before = format_count(&workbook);
+    for i in 0..1000 {
+        worksheet.write_url(i, 0, &format!("https://example.com/{}", i), None)?;
+    }
+    // No format was added, every cell uses the workbook's default hyperlink format.
+    assert_eq!(format_count(&workbook), formats_before);
+    let url_format = workbook.url_format().format;
+    for i in [0, 999].iter() {
+        unsafe {
+            let row = libxlsxwriter_sys::lxw_worksheet_find_row(worksheet.worksheet, *i);
+            let cell = libxlsxwriter_sys::lxw_worksheet_find_cell_in_row(row, 0);
+            assert_eq!((*cell).format, url_format);
+        }
+    }
+    workbook.close()?;
+    // The written file has a single cell format besides the default one.
+    let styles = read_xlsx_part(
+        "../target/test-worksheet_write_url-default_format.xlsx",
+        "xl/styles.xml",
+    );
+    assert!(styles.contains(r#"<cellXfs count="2">"#));
+    Ok(())
+}
+
+#[test]
+fn test_custom_color() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-format-custom_color.xlsx");
+    let format = workbook
+        .add_format()
+        .set_font_color(FormatColor::Custom(0x1F497D));
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_string(0, 0, "Brand color", Some(&format))?;
+    worksheet.set_tab_color(FormatColor::Custom(0xF79646));
+    assert_eq!(FormatColor::Custom(0xF79646).value(), 0xF79646);
+    workbook.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_show_gridlines() -> Result<(), XlsxError> {
+    for (screen, print) in &[(false, false), (true, false), (false, true), (true, true)] {
+        let workbook = Workbook::new(&format!(
+            "../target/test-worksheet_show_gridlines-{}-{}.xlsx",
+            screen, print
+        ));
+        let mut worksheet = workbook.add_worksheet(None)?;
+        worksheet.write_string(0, 0, "Gridlines", None)?;
+        // Start from the opposite state to check that both flags are set, not only turned on.
+        worksheet.show_gridlines(!*screen, !*print);
+        worksheet.show_gridlines(*screen, *print);
+        unsafe {
+            // screen_gridlines is written as showGridLines="0" when it is off, print_gridlines as
+            // <printOptions gridLines="1"/> when it is on.
+            let c_worksheet = &*worksheet.worksheet;
+            assert_eq!(c_worksheet.screen_gridlines, *screen as u8);
+            assert_eq!(c_worksheet.print_gridlines, *print as u8);
+        }
+        workbook.close()?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_filter_column() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_filter_column.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_string(0, 0, "Sales", None)?;
+    let values = [500., 1500., 1000., 2500., 800.];
+    for (i, value) in values.iter().enumerate() {
+        worksheet.write_number(i as WorksheetRow + 1, 0, *value, None)?;
+    }
+    worksheet.autofilter(0, 0, values.len() as WorksheetRow, 0)?;
+    worksheet.filter_column(0, &FilterRule::new(FilterCriteria::GreaterThan, 1000.))?;
+
+    let mut hidden = RowColOptions {
+        hidden: 1,
+        level: 0,
+        collapsed: 0,
+    };
+    for (i, value) in values.iter().enumerate() {
+        if *value <= 1000. {
+            worksheet.set_row_opt(i as WorksheetRow + 1, 15., None, &mut hidden)?;
+        }
+    }
+
+    // Filtering a column outside of the autofilter range is an error.
+    assert!(worksheet
+        .filter_column(1, &FilterRule::new(FilterCriteria::GreaterThan, 1000.))
+        .is_err());
+    workbook.close()
+}
+
+#[test]
+fn test_conditional_format_rule_strings() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-conditional_format_rule_strings.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..10 {
+        worksheet.write_number(i, 0, i.into(), None)?;
+    }
+    let mut conditional_format = ConditionalFormat::new(workbook.add_format())
+        .set_conditional_type(ConditionalType::TwoColorScale)
+        .set_min_rule_type(ConditionalRuleType::Formula)
+        .set_min_value_string(Some("=$A$1".to_string()))
+        .set_max_rule_type(ConditionalRuleType::Formula)
+        .set_max_value_string(Some("=$A$10".to_string()))
+        .set_multi_range(Some("A1:A10".to_string()));
+    worksheet.conditional_format_range(0, 0, 9, 0, &mut conditional_format)?;
+    // The strings passed to libxlsxwriter are kept alive by the rule and hold the values set above.
+    let internal_format = &conditional_format._internal_format;
+    let c_str = |ptr| unsafe { std::ffi::CStr::from_ptr(ptr) }.to_str().unwrap();
+    assert_eq!(c_str(internal_format.min_value_string), "=$A$1");
+    assert_eq!(c_str(internal_format.max_value_string), "=$A$10");
+    assert_eq!(c_str(internal_format.multi_range), "A1:A10");
+    assert!(internal_format.mid_value_string.is_null());
+    assert!(internal_format.value_string.is_null());
+    workbook.close()
+}
+
+#[test]
+fn test_color_scale() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-conditional_format_color_scale.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..10 {
+        worksheet.write_number(i, 0, i.into(), None)?;
+        worksheet.write_number(i, 1, i.into(), None)?;
+    }
+    let mut three_color = ConditionalFormat::three_color_scale(
+        FormatColor::Red,
+        FormatColor::Yellow,
+        FormatColor::Green,
+    );
+    worksheet.conditional_format_range(0, 0, 9, 0, &mut three_color)?;
+    let mut two_color = ConditionalFormat::two_color_scale(FormatColor::White, FormatColor::Blue)
+        .set_max_rule_type(ConditionalRuleType::Number)
+        .set_max_value(5.);
+    worksheet.conditional_format_range(0, 1, 9, 1, &mut two_color)?;
+    workbook.close()?;
+
+    let path = "../target/test-conditional_format_color_scale.xlsx";
+    let three_color = read_conditional_formatting(path, "A1:A10");
+    assert!(three_color.contains(r#"<cfRule type="colorScale""#));
+    assert!(three_color.contains(r#"<cfvo type="min""#));
+    assert!(three_color.contains(r#"<cfvo type="percentile" val="50"/>"#));
+    assert!(three_color.contains(r#"<cfvo type="max""#));
+    assert!(three_color
+        .contains(r#"<color rgb="FFFF0000"/><color rgb="FFFFFF00"/><color rgb="FF008000"/>"#));
+    let two_color = read_conditional_formatting(path, "B1:B10");
+    assert!(two_color.contains(r#"<cfvo type="min""#));
+    assert!(two_color.contains(r#"<cfvo type="num" val="5"/>"#));
+    assert!(!two_color.contains(r#"type="percentile""#));
+    assert!(two_color.contains(r#"<color rgb="FFFFFFFF"/><color rgb="FF0000FF"/>"#));
+    Ok(())
+}
+
+#[test]
+fn test_data_bar() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-conditional_format_data_bar.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..11 {
+        worksheet.write_number(i, 0, f64::from(i) - 5., None)?;
+    }
+    let mut data_bar = ConditionalFormat::data_bar(FormatColor::Red);
+    worksheet.conditional_format_range(0, 0, 10, 0, &mut data_bar)?;
+    workbook.close()?;
+
+    let data_bar =
+        read_conditional_formatting("../target/test-conditional_format_data_bar.xlsx", "A1:A11");
+    assert!(data_bar.contains(r#"<cfRule type="dataBar""#));
+    assert!(data_bar.contains(r#"<color rgb="FFFF0000"/>"#));
+    // The Excel 2010 extension is written, with a gradient bar that follows the worksheet direction.
+    let sheet = read_xlsx_part(
+        "../target/test-conditional_format_data_bar.xlsx",
+        "xl/worksheets/sheet1.xml",
+    );
+    let start = sheet.find("<x14:dataBar").unwrap();
+    let x14_data_bar = &sheet[start..start + sheet[start..].find('>').unwrap()];
+    assert!(!x14_data_bar.contains("gradient="));
+    assert!(!x14_data_bar.contains("direction="));
+    Ok(())
+}
+
+#[test]
+fn test_data_bar_full() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-conditional_format_data_bar_full.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..11 {
+        worksheet.write_number(i, 0, f64::from(i) - 5., None)?;
+    }
+    let mut data_bar =
+        ConditionalFormat::data_bar_full(FormatColor::Green, FormatColor::Red, FormatColor::Black);
+    assert_eq!(data_bar._internal_format.bar_solid, 1);
+    assert_eq!(
+        data_bar._internal_format.bar_border_color,
+        FormatColor::Black.value()
+    );
+    assert_eq!(
+        data_bar._internal_format.bar_negative_color,
+        FormatColor::Red.value()
+    );
+    assert_eq!(
+        data_bar._internal_format.bar_negative_border_color,
+        FormatColor::Red.value()
+    );
+    worksheet.conditional_format_range(0, 0, 10, 0, &mut data_bar)?;
+    workbook.close()
+}
+
+#[test]
+fn test_top_and_bottom() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-conditional_format_top_bottom.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let highlight = workbook.add_format().set_bg_color(FormatColor::Yellow);
+    for i in 0..20 {
+        worksheet.write_number(i, 0, i.into(), None)?;
+    }
+    let mut top = ConditionalFormat::top_n(3).set_format(&highlight);
+    assert_eq!(top._internal_format.value, 3.);
+    worksheet.conditional_format_range(0, 0, 19, 0, &mut top)?;
+    let mut bottom = ConditionalFormat::bottom_percent(10).set_format(&highlight);
+    assert_eq!(
+        bottom._internal_format.criteria,
+        ConditionalCriteria::TopOrBottomPercent.value()
+    );
+    worksheet.conditional_format_range(0, 0, 19, 0, &mut bottom)?;
+    workbook.close()
+}
+
+#[test]
+fn test_icon_set() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-conditional_format_icon_set.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..9 {
+        worksheet.write_number(i, 0, i.into(), None)?;
+    }
+    let mut icon_set =
+        ConditionalFormat::icon_set(ConditionalIconType::ThreeTrafficLightsRimmed).reversed();
+    assert_eq!(icon_set._internal_format.reverse_icons, 1);
+    assert_eq!(icon_set._internal_format.icons_only, 0);
+    worksheet.conditional_format_range(0, 0, 8, 0, &mut icon_set)?;
+    workbook.close()
+}
+
+#[test]
+fn test_protect_scenarios_and_pivot_tables() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_protect_scenarios.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let protection = Protection {
+        scenarios: true,
+        pivot_tables: true,
+        ..Protection::new()
+    };
+    let c_protection: libxlsxwriter_sys::lxw_protection = (&protection).into();
+    assert_eq!(c_protection.scenarios, 1);
+    assert_eq!(c_protection.pivot_tables, 1);
+    assert_eq!(c_protection.objects, 0);
+    worksheet.protect("password", &protection)?;
+
+    let mut worksheet2 = workbook.add_worksheet(None)?;
+    let c_protection: libxlsxwriter_sys::lxw_protection = (&Protection::new()).into();
+    assert_eq!(c_protection.scenarios, 0);
+    assert_eq!(c_protection.pivot_tables, 0);
+    worksheet2.protect("password", &Protection::new())?;
+    workbook.close()?;
+
+    let sheet_protection = |name: &str| {
+        let sheet = read_xlsx_part("../target/test-worksheet_protect_scenarios.xlsx", name);
+        let start = sheet.find("<sheetProtection ").unwrap();
+        let end = start + sheet[start..].find("/>").unwrap();
+        sheet[start..end].to_string()
+    };
+    // Allowing the edit of scenarios and pivot tables removes scenarios="1" and adds pivotTables="0".
+    let allowed = sheet_protection("xl/worksheets/sheet1.xml");
+    assert!(allowed.contains(r#"sheet="1""#));
+    assert!(allowed.contains(r#"objects="1""#));
+    assert!(!allowed.contains("scenarios="));
+    assert!(allowed.contains(r#"pivotTables="0""#));
+    let default = sheet_protection("xl/worksheets/sheet2.xml");
+    assert!(default.contains(r#"sheet="1""#));
+    assert!(default.contains(r#"objects="1""#));
+    assert!(default.contains(r#"scenarios="1""#));
+    assert!(!default.contains("pivotTables="));
+    Ok(())
+}
+
+#[test]
+fn test_fit_on_one_page() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_fit_on_one_page.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_string(0, 0, "Report", None)?;
+    worksheet.fit_on_one_page(Orientation::Landscape, PaperType::A4);
+    unsafe {
+        let c_worksheet = &*worksheet.worksheet;
+        // LXW_LANDSCAPE is 0 and LXW_PORTRAIT is 1.
+        assert_eq!(c_worksheet.orientation, 0);
+        assert_eq!(c_worksheet.paper_size, 9);
+        assert_eq!(c_worksheet.fit_page, 1);
+        assert_eq!(c_worksheet.fit_width, 1);
+        assert_eq!(c_worksheet.fit_height, 1);
+        assert_eq!(c_worksheet.margin_left, 0.25);
+        assert_eq!(c_worksheet.margin_right, 0.25);
+        assert_eq!(c_worksheet.margin_top, 0.75);
+        assert_eq!(c_worksheet.margin_bottom, 0.75);
+        assert_eq!(c_worksheet.hcenter, 1);
+    }
+    workbook.close()
+}
+
+#[test]
+fn test_validation_list_from_other_sheet() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-validation-list_from_formula.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let mut lists = workbook.add_worksheet(Some("Lists"))?;
+    for (i, name) in ["Apple", "Banana", "Cherry"].iter().enumerate() {
+        lists.write_string(i as WorksheetRow, 0, name, None)?;
+    }
+    let validation = DataValidation::list_from_formula("=Lists!$A$1:$A$3");
+    assert_eq!(validation.validate, DataValidationType::ListFormula);
+    worksheet.data_validation_range(0, 0, 9, 0, &validation)?;
+    workbook.close()
+}
+
+#[test]
+fn test_validation_date_between() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-validation-date_between.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let start = DateTime::new(2024, 1, 1, 0, 0, 0.);
+    let end = DateTime::new(2024, 12, 31, 0, 0, 0.);
+    let validation = DataValidation::date_between(&start, &end)
+        .error_message("Invalid date", "Enter a date in 2024");
+    let c_validation = validation.to_c_struct()?;
+    assert_eq!(c_validation.data_validation.minimum_datetime.year, 2024);
+    assert_eq!(c_validation.data_validation.maximum_datetime.month, 12);
+    assert_eq!(c_validation.data_validation.maximum_datetime.day, 31);
+    worksheet.data_validation_range(0, 0, 9, 0, &validation)?;
+
+    let validation = DataValidation::time_between(
+        &DateTime::new(0, 0, 0, 9, 0, 0.),
+        &DateTime::new(0, 0, 0, 17, 30, 0.),
+    );
+    worksheet.data_validation_range(0, 1, 9, 1, &validation)?;
+    workbook.close()
+}
+
+#[test]
+fn test_worksheet_index() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_index.xlsx");
+    workbook.add_worksheet(None)?;
+    workbook.add_worksheet(None)?;
+    let worksheet = workbook.add_worksheet(None)?;
+    assert_eq!(worksheet.index(), 2);
+    assert_eq!(workbook.get_worksheet("Sheet1").unwrap().index(), 0);
+
+    // An index that doesn't fit in a u16 panics instead of wrapping around. The index is restored before the workbook is closed.
+    unsafe { (*worksheet.worksheet).index = u32::from(u16::MAX) };
+    assert_eq!(worksheet.index(), u16::MAX);
+    unsafe { (*worksheet.worksheet).index = u32::from(u16::MAX) + 1 };
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| worksheet.index()));
+    unsafe { (*worksheet.worksheet).index = 2 };
+    assert!(result.is_err());
+    workbook.close()
+}
+
+#[test]
+fn test_validation_messages() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-validation-messages.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let validation = DataValidation::new(
+        DataValidationType::Decimal,
+        DataValidationCriteria::GreaterThan,
+        DataValidationErrorType::Stop,
+    )
+    .value(0.)
+    .input_message("Price", "Enter a positive price")
+    .error_message("Invalid price", "The price must be positive")
+    .error_type(DataValidationErrorType::Warning);
+    assert_eq!(validation.error_type, DataValidationErrorType::Warning);
+
+    let c_validation = validation.to_c_struct()?;
+    let read = |x: *mut std::os::raw::c_char| unsafe {
+        std::ffi::CStr::from_ptr(x).to_str().unwrap().to_string()
+    };
+    assert_eq!(read(c_validation.data_validation.input_title), "Price");
+    assert_eq!(
+        read(c_validation.data_validation.input_message),
+        "Enter a positive price"
+    );
+    assert_eq!(
+        read(c_validation.data_validation.error_title),
+        "Invalid price"
+    );
+    assert_eq!(
+        read(c_validation.data_validation.error_message),
+        "The price must be positive"
+    );
+    assert_eq!(c_validation.data_validation.show_input, 1);
+
+    worksheet.data_validation_cell(0, 0, &validation)?;
+    workbook.close()
+}
+
+#[test]
+fn test_empty_formula() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_empty_formula.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    assert!(worksheet.write_formula(0, 0, "", None).is_err());
+    assert!(worksheet.write_formula(0, 0, "  ", None).is_err());
+    assert!(worksheet.write_formula_num(0, 0, "", None, 1.).is_err());
+    assert!(worksheet.write_formula_str(0, 0, " ", None, "A").is_err());
+    assert!(worksheet.write_array_formula(0, 0, 1, 0, "", None).is_err());
+    let error = worksheet.write_formula(0, 0, "", None).unwrap_err();
+    assert_eq!(error.to_string(), "Formula is empty");
+
+    worksheet.write_formula(0, 0, "=1+1", None)?;
+    worksheet.write_formula_str(1, 0, "=\"A\"", None, "")?;
+    workbook.close()
+}
+
+fn arbitrary_string() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::prelude::*;
+    prop_oneof![
+        any::<String>(),
+        "[a-z=\u{0}]{0,16}",
+        proptest::collection::vec(any::<char>(), 32_760..32_780)
+            .prop_map(|x| x.into_iter().collect()),
+    ]
+}
+
+proptest::proptest! {
+    #![proptest_config(proptest::test_runner::Config::with_cases(64))]
+
+    #[test]
+    fn test_arbitrary_strings_do_not_panic(value in arbitrary_string()) {
+        let workbook = Workbook::new("../target/test-arbitrary_strings.xlsx");
+        let mut worksheet = workbook.add_worksheet(None).unwrap();
+        let _ = worksheet.write_string(0, 0, &value, None);
+        let _ = worksheet.write_formula(1, 0, &value, None);
+        let _ = worksheet.write_url(2, 0, &value, None);
+        let _ = worksheet.set_header(&value);
+        let _ = workbook.add_worksheet(Some(&value));
+        let _ = workbook.close();
+    }
+
+    #[test]
+    fn test_arbitrary_chart_strings_do_not_panic(value in arbitrary_string()) {
+        let workbook = Workbook::new("../target/test-arbitrary_chart_strings.xlsx");
+        let mut worksheet = workbook.add_worksheet(None).unwrap();
+        let mut chart = workbook.add_chart(ChartType::Line);
+        let _ = chart.add_series(Some(&value), Some(&value));
+        let _ = chart.add_series_range(Some((&value, 0, 0, 4, 0)), (&value, 0, 1, 4, 1));
+        if let Ok(mut series) = chart.add_series(None, Some("=Sheet1!$A$1:$A$5")) {
+            let _ = series.set_name(&value);
+            let _ = series.set_name_range(&value, 0, 0);
+            let _ = series.set_labels_num_format(&value);
+            let _ = series.set_trendline_name(&value);
+        }
+        let _ = chart.set_title_name(&value);
+        let _ = chart.set_title_name_range(&value, 0, 0);
+        let _ = chart.x_axis().set_name(&value);
+        let _ = chart.y_axis().set_num_format(&value);
+        let _ = worksheet.insert_chart(1, 3, &chart);
+        let _ = workbook.close();
+    }
+
+    #[test]
+    fn test_arbitrary_format_and_validation_strings_do_not_panic(value in arbitrary_string()) {
+        let workbook = Workbook::new("../target/test-arbitrary_format_strings.xlsx");
+        let mut worksheet = workbook.add_worksheet(None).unwrap();
+        if let Ok(format) = workbook.add_format().set_font_name(&value) {
+            let _ = worksheet.write_number(0, 0, 1., Some(&format));
+        }
+        if let Ok(format) = workbook.add_format().set_num_format(&value) {
+            let _ = worksheet.write_number(1, 0, 1., Some(&format));
+        }
+        let validation = DataValidation::list(&[&value, "b"])
+            .input_message(&value, &value)
+            .error_message(&value, &value);
+        let _ = worksheet.data_validation_cell(2, 0, &validation);
+        let _ = worksheet.data_validation_range(3, 0, 5, 0, &DataValidation::list_from_formula(&value));
+        let _ = worksheet.protect(&value, &Protection::new());
+        let _ = workbook.close();
+    }
+
+    #[test]
+    fn test_arbitrary_table_and_conditional_format_strings_do_not_panic(value in arbitrary_string()) {
+        let workbook = Workbook::new("../target/test-arbitrary_table_strings.xlsx");
+        let mut worksheet = workbook.add_worksheet(None).unwrap();
+        let _ = worksheet.add_table(
+            0,
+            0,
+            3,
+            1,
+            Some(TableOptions {
+                name: Some(value.clone()),
+                total_row: true,
+                columns: Some(vec![
+                    TableColumn {
+                        header: Some(value.clone()),
+                        total_string: Some(value.clone()),
+                        ..Default::default()
+                    },
+                    TableColumn {
+                        formula: Some(value.clone()),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            }),
+        );
+        let mut conditional_format = ConditionalFormat::new(workbook.add_format())
+            .set_conditional_type(ConditionalType::TwoColorScale)
+            .set_value_string(Some(value.clone()))
+            .set_min_value_string(Some(value.clone()))
+            .set_mid_value_string(Some(value.clone()))
+            .set_max_value_string(Some(value.clone()))
+            .set_multi_range(Some(value.clone()));
+        let _ = worksheet.conditional_format_range(5, 0, 9, 0, &mut conditional_format);
+        let _ = worksheet.conditional_format_cell(5, 1, &mut conditional_format);
+        let _ = workbook.close();
+    }
+}
+
+#[test]
+fn test_interior_nul_returns_error() -> Result<(), XlsxError> {
+    assert_eq!(
+        Workbook::try_new("../target/test-\0nul.xlsx")
+            .unwrap_err()
+            .error,
+        crate::error::STRING_CONTAINS_NUL
+    );
+    assert!(Workbook::new_with_options("../target/test-\0nul.xlsx", false, None, false).is_err());
+    assert_eq!(
+        Workbook::new_with_options("../target/test-nul.xlsx", false, Some("tmp\0dir"), false)
+            .unwrap_err()
+            .error,
+        crate::error::STRING_CONTAINS_NUL
+    );
+    let workbook = Workbook::new("../target/test-interior_nul.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    assert!(workbook.add_format().set_font_name("Ari\0al").is_err());
+    assert!(workbook.add_format().set_num_format("0.0\0").is_err());
+    let validation = DataValidation::list(&["a\0b", "c"]);
+    assert!(worksheet.data_validation_cell(0, 0, &validation).is_err());
+    assert!(worksheet.protect("pass\0word", &Protection::new()).is_err());
+    let mut chart = workbook.add_chart(ChartType::Column);
+    assert!(chart.add_series(None, Some("=Sheet1!$A$1:\0$A$5")).is_err());
+    let mut series = chart.add_series(None, None)?;
+    assert!(series.set_categories("Sheet\x001", 0, 0, 4, 0).is_err());
+    assert!(series.set_values("Sheet\x001", 0, 1, 4, 1).is_err());
+    assert!(series.set_name("Rev\0enue").is_err());
+    assert!(series.set_name_range("Sheet\x001", 0, 1).is_err());
+    assert!(chart.y_axis().set_num_format("#,#\0#0").is_err());
+    let table_column =
+        |header: &str, formula: Option<&str>, total_string: Option<&str>| TableColumn {
+            header: Some(header.to_string()),
+            formula: formula.map(|x| x.to_string()),
+            total_string: total_string.map(|x| x.to_string()),
+            ..Default::default()
+        };
+    for (name, column) in [
+        ("Sal\0es", table_column("Region", None, None)),
+        ("Sales", table_column("Reg\0ion", None, None)),
+        ("Sales", table_column("Region", Some("=1+\0"), None)),
+        ("Sales", table_column("Region", None, Some("To\0tal"))),
+    ] {
+        let result = worksheet.add_table(
+            2,
+            0,
+            4,
+            0,
+            Some(TableOptions {
+                name: Some(name.to_string()),
+                total_row: true,
+                columns: Some(vec![column]),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(result.unwrap_err().error, crate::error::STRING_CONTAINS_NUL);
+    }
+    let mut conditional_format = ConditionalFormat::new(workbook.add_format())
+        .set_conditional_type(ConditionalType::TwoColorScale)
+        .set_min_rule_type(ConditionalRuleType::Formula)
+        .set_min_value_string(Some("=$A$\x001".to_string()));
+    assert!(worksheet
+        .conditional_format_range(0, 0, 9, 0, &mut conditional_format)
+        .is_err());
+    let mut conditional_format = ConditionalFormat::new(workbook.add_format())
+        .set_conditional_type(ConditionalType::Cell)
+        .set_criteria(ConditionalCriteria::EqualTo)
+        .set_value_string(Some("\"a\0\"".to_string()))
+        .set_multi_range(Some("A1:A10 C1:\0C10".to_string()));
+    assert!(worksheet
+        .conditional_format_cell(0, 0, &mut conditional_format)
+        .is_err());
+    workbook.close()
+}
+
+#[test]
+fn test_add_table_style_number() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_add_table_style_number.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.add_table(
+        0,
+        0,
+        3,
+        1,
+        Some(TableOptions {
+            style_type: TableStyleType::Medium,
+            style_type_number: 7,
+            ..Default::default()
+        }),
+    )?;
+    let result = worksheet.add_table(
+        5,
+        0,
+        8,
+        1,
+        Some(TableOptions {
+            style_type: TableStyleType::Dark,
+            style_type_number: 20,
+            ..Default::default()
+        }),
+    );
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Table style number is out of range for the style type"
+    );
+    workbook.close()
+}
+
+#[test]
+fn test_chart_axis() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart_axis.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..5 {
+        worksheet.write_number(i, 0, (i * 20).into(), None)?;
+    }
+    let mut chart = workbook.add_chart(ChartType::Line);
+    chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    chart.x_axis().set_name("Step")?;
+    let mut y_axis = chart.y_axis();
+    y_axis.set_name("Progress")?;
+    assert!(y_axis.set_name("Pro\0gress").is_err());
+    y_axis.set_min(0.);
+    y_axis.set_max(100.);
+    worksheet.insert_chart(1, 3, &chart)?;
+
+    let mut log_chart = workbook.add_chart(ChartType::Line);
+    log_chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    log_chart.y_axis().set_log_base(10);
+    worksheet.insert_chart(20, 3, &log_chart)?;
+    workbook.close()
+}
+
+#[test]
+fn test_chart_axis_num_format() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart_axis-num_format.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..5 {
+        worksheet.write_number(i, 0, (i * 25_000).into(), None)?;
+    }
+    let mut chart = workbook.add_chart(ChartType::Column);
+    chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    let mut y_axis = chart.y_axis();
+    y_axis.set_num_format("#,##0")?;
+    // libxlsxwriter writes the stored format as the formatCode of the valAx numFmt element.
+    let num_format = unsafe { std::ffi::CStr::from_ptr((*y_axis.axis).num_format) };
+    assert_eq!(num_format.to_str().unwrap(), "#,##0");
+    worksheet.insert_chart(1, 3, &chart)?;
+    workbook.close()
+}
+
+#[test]
+fn test_write_array_formula_reversed_range() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_write_array_formula_range.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let result = worksheet.write_array_formula(6, 0, 4, 0, "{=TREND(C5:C7,B5:B7)}", None);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "First row or column of the range is after the last one"
+    );
+    assert!(worksheet
+        .write_array_formula(4, 1, 6, 0, "{=TREND(C5:C7,B5:B7)}", None)
+        .is_err());
+    worksheet.write_array_formula(4, 0, 6, 0, "{=TREND(C5:C7,B5:B7)}", None)?;
+    workbook.close()
+}
+
+#[test]
+fn test_chart_legend() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart_legend.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..5 {
+        worksheet.write_number(i, 0, (i * 10).into(), None)?;
+    }
+    let mut chart = workbook.add_chart(ChartType::Column);
+    chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    chart.set_legend_position(ChartLegendPosition::Bottom);
+    worksheet.insert_chart(1, 3, &chart)?;
+
+    let mut chart = workbook.add_chart(ChartType::Column);
+    chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    chart.delete_legend();
+    worksheet.insert_chart(20, 3, &chart)?;
+    workbook.close()
+}
+
+#[test]
+fn test_write_comment_opt_strings() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_write_comment_opt.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let author = std::ffi::CString::new("Reviewer").unwrap();
+    let font_name = std::ffi::CString::new("Courier New").unwrap();
+    let mut options = CommentOptions {
+        visible: 1,
+        author: author.as_ptr() as *mut _,
+        width: 200,
+        height: 80,
+        x_scale: 0.,
+        y_scale: 0.,
+        color: FormatColor::Yellow.value(),
+        font_name: font_name.as_ptr() as *mut _,
+        font_size: 10.,
+        font_family: 0,
+        start_row: 0,
+        start_col: 0,
+        x_offset: 0,
+        y_offset: 0,
+    };
+    worksheet.write_comment_opt(0, 0, "First", &mut options)?;
+    // The strings are copied by libxlsxwriter, so they can be dropped before the workbook is closed.
+    drop(author);
+    drop(font_name);
+    options.author = std::ptr::null_mut();
+    options.font_name = std::ptr::null_mut();
+    worksheet.write_comment_opt(1, 0, "Second", &mut options)?;
+    workbook.close()
+}
+
+#[test]
+fn test_chart_title() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart_title.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_string(0, 0, "Sales", None)?;
+    for i in 1..6 {
+        worksheet.write_number(i, 0, (i * 10).into(), None)?;
+    }
+    let mut chart = workbook.add_chart(ChartType::Column);
+    chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    chart.set_title_name("Static title")?;
+    assert!(chart.set_title_name("Static\0title").is_err());
+    worksheet.insert_chart(1, 3, &chart)?;
+
+    let mut chart = workbook.add_chart(ChartType::Column);
+    chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    chart.set_title_name_range("Sheet1", 0, 0)?;
+    assert!(chart.set_title_name_range("She\0et1", 0, 0).is_err());
+    worksheet.insert_chart(20, 3, &chart)?;
+
+    let mut chart = workbook.add_chart(ChartType::Column);
+    chart.add_series(None, Some("=Sheet1!$A$2:$A$6"))?;
+    chart.set_title_none();
+    worksheet.insert_chart(40, 3, &chart)?;
+    workbook.close()
+}
+
+#[test]
+fn test_chart_types() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart_types.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..5 {
+        worksheet.write_number(i, 0, (i * 10).into(), None)?;
+        worksheet.write_number(i, 1, (i * 10 + 2).into(), None)?;
+    }
+    let chart_types = [
+        ChartType::Area,
+        ChartType::AreaStacked,
+        ChartType::AreaStackedPercent,
+        ChartType::Bar,
+        ChartType::BarStacked,
+        ChartType::BarStackedPercent,
+        ChartType::Column,
+        ChartType::ColumnStacked,
+        ChartType::ColumnStackedPercent,
+        ChartType::Doughnut,
+        ChartType::Line,
+        ChartType::LineStacked,
+        ChartType::LineStackedPercent,
+        ChartType::Pie,
+        ChartType::Scatter,
+        ChartType::ScatterStraight,
+        ChartType::ScatterStraightWithMarkers,
+        ChartType::ScatterSmooth,
+        ChartType::ScatterSmoothWithMarkers,
+        ChartType::Radar,
+        ChartType::RadarWithMarkers,
+        ChartType::RadarFilled,
+    ];
+    for (i, chart_type) in chart_types.iter().enumerate() {
+        let mut chart = workbook.add_chart(*chart_type);
+        chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+        chart.add_series(None, Some("=Sheet1!$B$1:$B$5"))?;
+        worksheet.insert_chart(i as WorksheetRow * 20, 3, &chart)?;
+    }
+    workbook.close()
+}
+
+#[test]
+fn test_margins() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet_margins.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.set_margins(0.3, 0.4, 0.5, 0.6);
+    assert_eq!(worksheet.margins(), (0.3, 0.4, 0.5, 0.6));
+
+    worksheet.set_margins_cm(2.54, 1.27, 5.08, 0.);
+    let (left, right, top, bottom) = worksheet.margins();
+    assert!((left - 1.).abs() < 1e-9);
+    assert!((right - 0.5).abs() < 1e-9);
+    assert!((top - 2.).abs() < 1e-9);
+    assert!(bottom.abs() < 1e-9);
+    workbook.close()
+}
+
+#[test]
+fn test_chart_series_labels() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart_series-labels.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..4 {
+        worksheet.write_string(i, 0, &format!("Item {}", i + 1), None)?;
+        worksheet.write_number(i, 1, (i * 10 + 5).into(), None)?;
+    }
+    let mut chart = workbook.add_chart(ChartType::Pie);
+    let mut series = chart.add_series(Some("=Sheet1!$A$1:$A$4"), Some("=Sheet1!$B$1:$B$4"))?;
+    series.set_labels();
+    series.set_labels_options(false, true, false);
+    series.set_labels_percentage();
+    series.set_labels_separator(ChartLabelSeparator::Newline);
+    series.set_labels_position(ChartLabelPosition::BestFit);
+    series.set_labels_leader_line();
+    series.set_labels_num_format("0.0%")?;
+    assert!(series.set_labels_num_format("0.0\0%").is_err());
+    unsafe {
+        let raw = &*series.chart_series;
+        assert_eq!(raw.show_labels, 1);
+        assert_eq!(raw.show_labels_percent, 1);
+        assert_eq!(raw.show_labels_category, 1);
+        assert_eq!(raw.show_labels_value, 0);
+        assert_eq!(raw.show_labels_leader, 1);
+        assert_eq!(raw.label_position, ChartLabelPosition::BestFit.value());
+        assert_eq!(raw.label_separator, ChartLabelSeparator::Newline.value());
+    }
+    worksheet.insert_chart(1, 3, &chart)?;
+    workbook.close()
+}
+
+#[test]
+fn test_chart_series_marker() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart_series-marker.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..5 {
+        worksheet.write_number(i, 0, (i * 3 % 5).into(), None)?;
+    }
+    let mut chart = workbook.add_chart(ChartType::Line);
+    let mut series = chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    series.set_marker(ChartMarkerType::MarkerDiamond, 9);
+    unsafe {
+        let marker = &*(*series.chart_series).marker;
+        assert_eq!(marker.type_, ChartMarkerType::MarkerDiamond.value());
+        assert_eq!(marker.size, 9);
+    }
+    worksheet.insert_chart(1, 3, &chart)?;
+    workbook.close()
+}
+
+#[test]
+fn test_conditional_format_without_format() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-conditional_format_without_format.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..10 {
+        worksheet.write_number(i, 0, i.into(), None)?;
+    }
+    let mut color_scale = ConditionalFormat::new_typed(ConditionalType::TwoColorScale)
+        .set_min_rule_type(ConditionalRuleType::Minimum)
+        .set_min_color(FormatColor::White)
+        .set_max_rule_type(ConditionalRuleType::Maximum)
+        .set_max_color(FormatColor::Green);
+    assert!(color_scale._internal_format.format.is_null());
+    assert_eq!(
+        color_scale._internal_format.type_,
+        ConditionalType::TwoColorScale.value()
+    );
+    worksheet.conditional_format_range(0, 0, 9, 0, &mut color_scale)?;
+    workbook.close()
+}
+
+#[test]
+fn test_try_write() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-try_write.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    assert!(!worksheet.try_write(0, 0, CellValue::Number(1.), None)?);
+    assert!(worksheet.try_write(0, 0, CellValue::String("second".to_string()), None)?);
+    assert!(!worksheet.try_write(0, 1, CellValue::Bool(true), None)?);
+    worksheet.write_number(1, 0, 2., None)?;
+    assert!(worksheet.try_write(1, 0, CellValue::Number(3.), None)?);
+    workbook.close()
+}
+
+#[test]
+fn test_chart_series_trendline() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart_series-trendline.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..6 {
+        worksheet.write_number(i, 0, i.into(), None)?;
+        worksheet.write_number(i, 1, (i * 2 + i % 2).into(), None)?;
+    }
+    let mut chart = workbook.add_chart(ChartType::Scatter);
+    let mut series = chart.add_series(Some("=Sheet1!$A$1:$A$6"), Some("=Sheet1!$B$1:$B$6"))?;
+    series.set_trendline(ChartTrendlineType::Linear, 0);
+    series.set_trendline_equation();
+    series.set_trendline_name("Linear fit")?;
+    assert!(series.set_trendline_name("Linear\0fit").is_err());
+    unsafe {
+        let raw = &*series.chart_series;
+        assert_eq!(raw.has_trendline, 1);
+        assert_eq!(raw.trendline_type, ChartTrendlineType::Linear.value());
+        assert_eq!(raw.has_trendline_equation, 1);
+        assert_eq!(raw.has_trendline_r_squared, 0);
+    }
+    worksheet.insert_chart(1, 3, &chart)?;
+    workbook.close()
+}
+
+#[test]
+fn test_chart_size_and_offset() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart-size_and_offset.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..5 {
+        worksheet.write_number(i, 0, (i * 10).into(), None)?;
+    }
+    let mut chart = workbook.add_chart(ChartType::Column);
+    chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    assert_eq!(chart.size_scale(), (1., 1.));
+    chart.set_size(960, 288);
+    assert_eq!(chart.size_scale(), (2., 1.));
+    worksheet.insert_chart_opt(
+        1,
+        3,
+        &chart,
+        &ImageOptions {
+            x_offset: 15,
+            y_offset: 10,
+            x_scale: 1.,
+            y_scale: 1.,
+            ..Default::default()
+        },
+    )?;
+    workbook.close()
+}
+
+#[test]
+fn test_zoom_and_print_scale_range() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-zoom_and_print_scale.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for scale in &[0, 1, 9, 401, 1000, u16::MAX] {
+        assert_eq!(
+            worksheet.set_zoom(*scale).unwrap_err().error,
+            crate::error::SCALE_OUT_OF_RANGE
+        );
+        assert_eq!(
+            worksheet.set_print_scale(*scale).unwrap_err().error,
+            crate::error::SCALE_OUT_OF_RANGE
+        );
+    }
+    for scale in &[10, 400] {
+        worksheet.set_zoom(*scale)?;
+        worksheet.set_print_scale(*scale)?;
+    }
+    // A rejected zoom leaves the previous one in place.
+    worksheet.set_zoom(150)?;
+    assert!(worksheet.set_zoom(1000).is_err());
+    unsafe {
+        assert_eq!((*worksheet.worksheet).zoom, 150);
+    }
+    workbook.close()
+}
+
+#[test]
+fn test_chart_combine() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart-combine.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..5 {
+        worksheet.write_number(i, 0, (i * 1000).into(), None)?;
+        worksheet.write_number(i, 1, f64::from(i) / 10., None)?;
+    }
+    let mut column_chart = workbook.add_chart(ChartType::Column);
+    column_chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    let mut line_chart = workbook.add_chart(ChartType::Line);
+    line_chart.add_series(None, Some("=Sheet1!$B$1:$B$5"))?;
+    column_chart.combine(&line_chart)?;
+    worksheet.insert_chart(1, 3, &column_chart)?;
+    unsafe {
+        assert_eq!((*column_chart.chart).combined, line_chart.chart);
+    }
+
+    // The combined chart is written with the primary chart, so it must belong to the same workbook.
+    let other_workbook = Workbook::new("../target/test-chart-combine-other_workbook.xlsx");
+    let other_chart = other_workbook.add_chart(ChartType::Line);
+    let mut bar_chart = workbook.add_chart(ChartType::Bar);
+    assert_eq!(
+        bar_chart.combine(&other_chart).unwrap_err().error,
+        crate::error::CHART_FROM_OTHER_WORKBOOK
+    );
+    unsafe {
+        assert!((*bar_chart.chart).combined.is_null());
+    }
+    other_workbook.close()?;
+    workbook.close()
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_datetime_from_chrono() -> Result<(), XlsxError> {
+    use std::convert::TryFrom;
+
+    let date = chrono::NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+    assert_eq!(
+        DateTime::try_from(date)?,
+        DateTime::new(2021, 2, 28, 0, 0, 0.)
+    );
+
+    let datetime = chrono::NaiveDate::from_ymd_opt(1999, 12, 31)
+        .unwrap()
+        .and_hms_milli_opt(23, 59, 58, 500)
+        .unwrap();
+    assert_eq!(
+        DateTime::try_from(datetime)?,
+        DateTime::new(1999, 12, 31, 23, 59, 58.5)
+    );
+
+    let out_of_range = chrono::NaiveDate::from_ymd_opt(40000, 1, 1).unwrap();
+    assert_eq!(
+        DateTime::try_from(out_of_range).unwrap_err().error,
+        crate::error::DATETIME_OUT_OF_RANGE
+    );
+    Ok(())
+}
+
+#[test]
+fn test_external_link_url() -> Result<(), XlsxError> {
+    assert_eq!(
+        external_link_url("../data/foo.xlsx"),
+        "external:..\\data\\foo.xlsx"
+    );
+    assert_eq!(
+        external_link_url("file:///C:/temp/foo.xlsx"),
+        "external:C:\\temp\\foo.xlsx"
+    );
+    assert_eq!(
+        external_link_url("file://server/share/foo.xlsx#Sheet2!A1"),
+        "external:\\\\server\\share\\foo.xlsx#Sheet2!A1"
+    );
+    assert_eq!(
+        external_link_url("c:\\temp\\foo.xlsx"),
+        "external:c:\\temp\\foo.xlsx"
+    );
+    assert_eq!(
+        external_link_url("../data/foo.xlsx#'My/Sheet'!A1"),
+        "external:..\\data\\foo.xlsx#'My/Sheet'!A1"
+    );
+    assert_eq!(
+        external_link_url("/home/user/foo.xlsx#Sheet2!A1"),
+        "external:/home/user/foo.xlsx#Sheet2!A1"
+    );
+    assert_eq!(
+        external_link_url("file:///C:/My%20Documents/foo%2Bbar.xlsx"),
+        "external:C:\\My Documents\\foo+bar.xlsx"
+    );
+    assert_eq!(
+        external_link_url("file:///tmp/my%20file.xlsx#Sheet2!A1"),
+        "external:/tmp/my file.xlsx#Sheet2!A1"
+    );
+    assert_eq!(
+        external_link_url("file:///tmp/100%.xlsx"),
+        "external:/tmp/100%.xlsx"
+    );
+
+    let workbook = Workbook::new("../target/test-worksheet-write_external_link.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_external_link(0, 0, "../data/foo.xlsx", None)?;
+    worksheet.write_external_link(1, 0, "file:///C:/temp/foo.xlsx", None)?;
+    workbook.close()
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_datetime_from_time() -> Result<(), XlsxError> {
+    use std::convert::TryFrom;
+
+    let date = time::Date::from_calendar_date(2020, time::Month::February, 29).unwrap();
+    assert_eq!(
+        DateTime::try_from(date)?,
+        DateTime::new(2020, 2, 29, 0, 0, 0.)
+    );
+
+    let datetime =
+        time::PrimitiveDateTime::new(date, time::Time::from_hms_milli(13, 45, 30, 250).unwrap());
+    assert_eq!(
+        DateTime::try_from(datetime)?,
+        DateTime::new(2020, 2, 29, 13, 45, 30.25)
+    );
+    Ok(())
+}
+
+/// Size of an image held in memory, see `image_file_dimensions()`.
+fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    crate::image::read_image_dimensions(std::io::Cursor::new(data)).unwrap()
+}
+
+#[test]
+fn test_insert_image_centered() -> Result<(), XlsxError> {
+    let mut icon = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    icon.extend_from_slice(&16u32.to_be_bytes());
+    icon.extend_from_slice(&16u32.to_be_bytes());
+    let icon_size = image_dimensions(&icon).unwrap();
+    assert_eq!(icon_size, (16, 16));
+
+    let workbook = Workbook::new("../target/test-worksheet-insert_image_centered.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let default_cell = worksheet.cell_size_pixels(0, 0);
+    assert_eq!(default_cell, (64, 20));
+    assert_eq!(
+        centered_image_options(icon_size, default_cell),
+        ImageOptions {
+            x_offset: 24,
+            y_offset: 2,
+            x_scale: 1.,
+            y_scale: 1.,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        centered_image_options((128, 40), default_cell),
+        ImageOptions {
+            x_offset: 0,
+            y_offset: 0,
+            x_scale: 0.5,
+            y_scale: 0.5,
+            ..Default::default()
+        }
+    );
+
+    worksheet.set_column(1, 1, 30., None)?;
+    worksheet.set_row(1, 60., None)?;
+    assert_eq!(worksheet.cell_size_pixels(1, 1), (215, 80));
+    worksheet.insert_image_centered(1, 1, "../images/simple1.png")?;
+
+    // A missing file isn't reported as invalid parameters.
+    assert_eq!(
+        worksheet
+            .insert_image_centered(2, 1, "../images/does-not-exist.png")
+            .unwrap_err()
+            .error,
+        crate::error::IMAGE_FILE_NOT_READABLE
+    );
+    let not_an_image = "../target/test-worksheet-insert_image_centered.txt";
+    std::fs::write(not_an_image, "not an image").unwrap();
+    assert_eq!(
+        worksheet
+            .insert_image_centered(2, 1, not_an_image)
+            .unwrap_err()
+            .error,
+        libxlsxwriter_sys::lxw_error_LXW_ERROR_IMAGE_DIMENSIONS
+    );
+    workbook.close()
+}
+
+#[test]
+fn test_insert_image_fit_to_range() -> Result<(), XlsxError> {
+    // A 200x100 image in a 100x100 range.
+    assert_eq!(
+        fit_image_options((200, 100), (100, 100), FitMode::Stretch),
+        ImageOptions {
+            x_scale: 0.5,
+            y_scale: 1.,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        fit_image_options((200, 100), (100, 100), FitMode::Contain),
+        ImageOptions {
+            x_offset: 0,
+            y_offset: 25,
+            x_scale: 0.5,
+            y_scale: 0.5,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        fit_image_options((200, 100), (100, 100), FitMode::Cover),
+        ImageOptions {
+            x_scale: 1.,
+            y_scale: 1.,
+            ..Default::default()
+        }
+    );
+    // Small images are scaled up.
+    assert_eq!(
+        fit_image_options((16, 16), (64, 40), FitMode::Contain),
+        ImageOptions {
+            x_offset: 12,
+            y_offset: 0,
+            x_scale: 2.5,
+            y_scale: 2.5,
+            ..Default::default()
+        }
+    );
+
+    let workbook = Workbook::new("../target/test-worksheet-insert_image_fit_to_range.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.set_column(1, 1, 30., None)?;
+    assert_eq!(worksheet.range_size_pixels(0, 0, 4, 1), (64 + 215, 100));
+    for (i, mode) in [FitMode::Stretch, FitMode::Contain, FitMode::Cover]
+        .iter()
+        .enumerate()
+    {
+        let row = i as WorksheetRow * 10;
+        worksheet.insert_image_fit_to_range(row, 0, row + 4, 1, "../images/simple1.png", *mode)?;
+    }
+    assert!(worksheet
+        .insert_image_fit_to_range(4, 1, 0, 0, "../images/simple1.png", FitMode::Cover)
+        .is_err());
+    assert_eq!(
+        worksheet
+            .insert_image_fit_to_range(0, 3, 4, 4, "../images/does-not-exist.png", FitMode::Cover)
+            .unwrap_err()
+            .error,
+        crate::error::IMAGE_FILE_NOT_READABLE
+    );
+    workbook.close()
+}
+
+#[test]
+fn test_column_width_and_row_height_pixels() {
+    use crate::worksheet::{column_width_pixels, row_height_pixels};
+    // Pixel sizes Excel shows for these column widths and row heights at 96 DPI with the default font.
+    for (width, pixels) in [
+        (0., 0),
+        (0.5, 6),
+        (0.92, 11),
+        (1., 12),
+        (8.43, 64),
+        (10., 75),
+        (20., 145),
+        (30., 215),
+        (50.71, 360),
+        (255., 1790),
+    ] {
+        assert_eq!(column_width_pixels(width), pixels, "column width {}", width);
+    }
+    for (height, pixels) in [(0., 0), (12.75, 17), (15., 20), (60., 80), (409., 545)] {
+        assert_eq!(row_height_pixels(height), pixels, "row height {}", height);
+    }
+    assert_eq!(column_width_pixels(8.43), LXW_DEF_COL_WIDTH_PIXELS);
+    assert_eq!(row_height_pixels(15.), LXW_DEF_ROW_HEIGHT_PIXELS);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_datetime_serde() {
+    let datetime = DateTime::new(2021, 7, 4, 18, 30, 15.5);
+    let json = serde_json::to_string(&datetime).unwrap();
+    assert_eq!(
+        json,
+        r#"{"year":2021,"month":7,"day":4,"hour":18,"min":30,"second":15.5}"#
+    );
+    let parsed: DateTime = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, datetime);
+}
+
+#[test]
+fn test_insert_chart_workbook() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-chart-workbook.xlsx");
+    let other_workbook = Workbook::new("../target/test-chart-other_workbook.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let mut chartsheet = workbook.add_chartsheet(None)?;
+    for i in 0..5 {
+        worksheet.write_number(i, 0, (i * 10).into(), None)?;
+    }
+
+    let mut chart = workbook.add_chart(ChartType::Column);
+    chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    worksheet.insert_chart(1, 3, &chart)?;
+
+    let mut other_chart = other_workbook.add_chart(ChartType::Line);
+    other_chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    assert_eq!(
+        worksheet
+            .insert_chart(20, 3, &other_chart)
+            .unwrap_err()
+            .error,
+        crate::error::CHART_FROM_OTHER_WORKBOOK
+    );
+    assert_eq!(
+        chartsheet.set_chart(&other_chart).unwrap_err().error,
+        crate::error::CHART_FROM_OTHER_WORKBOOK
+    );
+
+    let mut chart2 = workbook.add_chart(ChartType::Line);
+    chart2.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    chartsheet.set_chart(&chart2)?;
+    workbook.close()
+}
+
+#[test]
+fn test_write_row_and_column() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-write_row_and_column.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_row_strings(0, 1, &["a", "b", "c", "d", "e"], None)?;
+    worksheet.write_row_numbers(1, 1, &[1., 2., 3., 4., 5.], None)?;
+    worksheet.write_column_strings(2, 0, &["v", "w", "x", "y", "z"], None)?;
+    worksheet.write_column_numbers(2, 1, &[10., 20., 30., 40., 50.], None)?;
+    for i in 0..5 {
+        assert!(worksheet.try_write(1, 1 + i, CellValue::Blank, None)?);
+        assert!(worksheet.try_write(2 + u32::from(i), 1, CellValue::Blank, None)?);
+    }
+
+    let err = worksheet
+        .write_row_numbers(3, 16382, &[1., 2., 3.], None)
+        .unwrap_err();
+    assert_eq!(err.cell(), Some((3, 16384)));
+    let err = worksheet
+        .write_column_strings(1_048_574, 5, &["a", "b", "c"], None)
+        .unwrap_err();
+    assert_eq!(err.cell(), Some((1_048_576, 5)));
+    workbook.close()
+}
+
+#[test]
+fn test_write_cell_value() -> Result<(), XlsxError> {
+    assert_eq!(CellValue::from(1.5), CellValue::Number(1.5));
+    assert_eq!(CellValue::from(7u16), CellValue::Number(7.));
+    assert_eq!(CellValue::from(-3), CellValue::Number(-3.));
+    assert_eq!(CellValue::from(true), CellValue::Bool(true));
+    assert_eq!(CellValue::from("a"), CellValue::String("a".to_string()));
+
+    let workbook = Workbook::new("../target/test-worksheet-write.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let row = vec![
+        CellValue::String("Item".to_string()),
+        CellValue::Number(12.5),
+        CellValue::Bool(false),
+        CellValue::Formula("=B1*2".to_string()),
+        CellValue::DateTime(DateTime::new(2021, 3, 14, 15, 9, 26.)),
+        CellValue::Blank,
+    ];
+    for (col, value) in row.into_iter().enumerate() {
+        worksheet.write(0, col as WorksheetCol, value, None)?;
+    }
+    worksheet.write(1, 0, "text", None)?;
+    worksheet.write(1, 1, 3u8, None)?;
+    let date = DateTime::new(2021, 3, 14, 0, 0, 0.);
+    worksheet.write(1, 2, &date, None)?;
+    assert_eq!(CellValue::from(&date), CellValue::DateTime(date));
+    for col in 0..5 {
+        assert!(worksheet.try_write(0, col, CellValue::Blank, None)?);
+    }
+    workbook.close()
+}
+
+#[test]
+fn test_write_datetime_seconds() -> Result<(), XlsxError> {
+    let datetime = DateTime::new(2013, 2, 28, 12, 0, 0.999);
+    let serial = |datetime: &DateTime| unsafe {
+        libxlsxwriter_sys::lxw_datetime_to_excel_datetime(&mut datetime.into())
+    };
+    let expected = 41333.5 + 0.999 / 86400.;
+    assert!((serial(&datetime) - expected).abs() < 1e-9);
+    assert_eq!(datetime.with_whole_seconds().second, 0.);
+    assert_eq!(serial(&datetime.with_whole_seconds()), 41333.5);
+
+    let workbook = Workbook::new("../target/test-worksheet-write_datetime_seconds.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let format = workbook
+        .add_format()
+        .set_num_format("yyyy-mm-dd hh:mm:ss.000")?;
+    worksheet.write_datetime(0, 0, &datetime, Some(&format))?;
+    worksheet.write_datetime_seconds(1, 0, &datetime, Some(&format))?;
+    workbook.close()
+}
+
+#[test]
+fn test_report_builder() -> Result<(), XlsxError> {
+    let path = "../target/test-report_builder.xlsx";
+    let mut report = ReportBuilder::new(path);
+    report
+        .style("header", |format| Ok(format.set_bold()))
+        .style("money", |format| format.set_num_format("$#,##0.00"));
+    report
+        .sheet("Sales")
+        .table(
+            &["Region", "Revenue"],
+            vec![
+                vec!["North".into(), 1250.5.into()],
+                vec!["South".into(), 980.into()],
+            ],
+        )
+        .header_style("header")
+        .column_style(1, "money")
+        .table_style(TableStyleType::Medium, 9)
+        .freeze_header();
+    report
+        .sheet("Staff")
+        .table(&["Name", "Start date", "Active"], vec![])
+        .header_style("header");
+    assert_eq!(report.sheets.len(), 2);
+    assert!(report.sheets[0].freeze_header);
+    assert_eq!(report.sheet("Sales").rows.len(), 2);
+    assert_eq!(report.sheets.len(), 2);
+    report.finish()?;
+    assert!(std::path::Path::new(path).exists());
+
+    let mut report = ReportBuilder::new("../target/test-report_builder-unknown_style.xlsx");
+    report
+        .sheet("Sheet1")
+        .table(&["A"], vec![vec![1.into()]])
+        .column_style(0, "missing");
+    let err = report.finish().unwrap_err();
+    assert_eq!(err.error, crate::error::UNKNOWN_STYLE);
+
+    let mut report = ReportBuilder::new("../target/test-report_builder-too_many_cells.xlsx");
+    report
+        .sheet("Sheet1")
+        .table(&["A"], vec![vec![1.into(), 2.into()]]);
+    let err = report.finish().unwrap_err();
+    assert_eq!(err.error, crate::error::NUMBER_OF_COLUMNS_IS_NOT_MATCHED);
+    assert_eq!(err.cell(), Some((1, 1)));
+    Ok(())
+}
+
+#[test]
+fn test_write_non_finite_number() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-write_non_finite_number.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for (row, number) in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY]
+        .iter()
+        .enumerate()
+    {
+        let err = worksheet
+            .write_number(row as WorksheetRow, 0, *number, None)
+            .unwrap_err();
+        assert_eq!(err.error, crate::error::NON_FINITE_NUMBER);
+        assert_eq!(err.cell(), Some((row as WorksheetRow, 0)));
+        // Nothing was written, so the cell is still free.
+        assert!(!worksheet.try_write(row as WorksheetRow, 0, CellValue::Blank, None)?);
+    }
+    assert!(worksheet.write(0, 1, f64::NAN, None).is_err());
+    assert!(worksheet
+        .try_write(0, 2, CellValue::Number(f64::INFINITY), None)
+        .is_err());
+    worksheet.write_number(0, 3, f64::MAX, None)?;
+    workbook.close()
+}
+
+#[test]
+fn test_write_non_finite_cell_value_strict() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-write_non_finite_strict.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let format = workbook.add_format().set_bold();
+    let err = worksheet
+        .write(1, 2, CellValue::Number(f64::NAN), Some(&format))
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::NON_FINITE_NUMBER);
+    assert_eq!(err.cell(), Some((1, 2)));
+    assert!(!worksheet.try_write(1, 2, CellValue::Blank, None)?);
+    workbook.close()
+}
+
+#[test]
+fn test_write_non_finite_cell_value_lax() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-write_non_finite_lax.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    workbook.set_non_finite_as_blank(true);
+    let format = workbook.add_format().set_bold();
+    worksheet.write(1, 2, CellValue::Number(f64::NAN), Some(&format))?;
+    worksheet.write_with_comment(2, 2, CellValue::Number(f64::INFINITY), Some(&format), "Inf")?;
+    // A formatted blank cell was written in place of the number.
+    assert!(worksheet.try_write(1, 2, CellValue::Blank, None)?);
+    assert!(worksheet.try_write(2, 2, CellValue::Blank, None)?);
+    worksheet.write(3, 2, CellValue::Number(f64::NEG_INFINITY), None)?;
+    // write_number() itself stays strict.
+    assert!(worksheet.write_number(4, 2, f64::NAN, None).is_err());
+    workbook.close()
+}
+
+#[test]
+fn test_write_rich_string_invalid() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-write_rich_string_invalid.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let bold = workbook.add_format().set_bold();
+    let italic = workbook.add_format().set_italic();
+
+    let err = worksheet
+        .write_rich_string(0, 0, &[("This is ", None), ("", None)], None)
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::INVALID_RICH_STRING);
+    assert_eq!(err.fragment(), Some(1));
+    assert_eq!(err.cell(), Some((0, 0)));
+
+    // Two consecutive formats, bold has no text of its own.
+    let err = worksheet
+        .write_rich_string(
+            1,
+            0,
+            &[
+                ("This is ", None),
+                ("", Some(&bold)),
+                ("italic", Some(&italic)),
+            ],
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(err.fragment(), Some(1));
+    assert_eq!(
+        err.to_string(),
+        "Rich string is empty or contains an empty fragment (row 1, column 0) (fragment 1)"
+    );
+
+    let err = worksheet.write_rich_string(2, 0, &[], None).unwrap_err();
+    assert_eq!(err.error, crate::error::INVALID_RICH_STRING);
+    assert_eq!(err.fragment(), None);
+
+    worksheet.write_rich_string(3, 0, &[("bold", Some(&bold)), (" text", None)], None)?;
+    workbook.close()
+}
+
+#[test]
+fn test_merge_range_variants() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-merge_range_variants.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let format = workbook
+        .add_format()
+        .set_bold()
+        .set_align(FormatAlignment::Center);
+    let date_format = workbook.add_format().set_num_format("yyyy-mm-dd")?;
+    worksheet.merge_range_number(0, 0, 0, 3, 1234.5, Some(&format))?;
+    worksheet.merge_range_datetime(
+        1,
+        0,
+        1,
+        3,
+        &DateTime::new(2021, 3, 14, 0, 0, 0.),
+        Some(&date_format),
+    )?;
+    worksheet.merge_range_blank(2, 0, 3, 3, Some(&format))?;
+    // The value is in the top-left cell of each merged range.
+    assert!(worksheet.try_write(0, 0, CellValue::Number(1.), Some(&format))?);
+    assert!(worksheet.try_write(1, 0, CellValue::Number(1.), Some(&date_format))?);
+    assert!(worksheet.try_write(2, 0, CellValue::Blank, Some(&format))?);
+    assert!(worksheet
+        .merge_range_number(4, 0, 4, 1, f64::NAN, None)
+        .is_err());
+    assert!(!worksheet.try_write(4, 0, CellValue::Blank, None)?);
+    workbook.close()
+}
+
+#[test]
+fn test_worksheet_name() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-name.xlsx");
+    let worksheet1 = workbook.add_worksheet(None)?;
+    let worksheet2 = workbook.add_worksheet(Some("Data"))?;
+    let worksheet3 = workbook.add_worksheet(None)?;
+    assert_eq!(worksheet1.name(), "Sheet1");
+    assert_eq!(worksheet2.name(), "Data");
+    assert_eq!(worksheet3.name(), "Sheet3");
+    workbook.close()
+}
+
+#[test]
+fn test_custom_rgb_color() -> Result<(), XlsxError> {
+    let color = FormatColor::rgb(0x1A2B3C);
+    assert_eq!(color.value(), 0x1A2B3C);
+    assert_eq!(FormatColor::rgb(0xFF1A2B3C).value(), 0x1A2B3C);
+    assert_eq!(FormatColor::rgb(0xFFFFFF).value(), 0xFFFFFF);
+    // 0x000000 means "no color" to libxlsxwriter.
+    assert_eq!(FormatColor::rgb(0).value(), FormatColor::Black.value());
+
+    let scale = ConditionalFormat::three_color_scale(
+        FormatColor::rgb(0xF8696B),
+        FormatColor::rgb(0x1A2B3C),
+        FormatColor::rgb(0x63BE7B),
+    );
+    assert_eq!(scale._internal_format.min_color, 0xF8696B);
+    assert_eq!(scale._internal_format.mid_color, 0x1A2B3C);
+    assert_eq!(scale._internal_format.max_color, 0x63BE7B);
+    let bar = ConditionalFormat::data_bar(color).set_bar_axis_color(FormatColor::rgb(0x808080));
+    assert_eq!(bar._internal_format.bar_color, 0x1A2B3C);
+    assert_eq!(bar._internal_format.bar_axis_color, 0x808080);
+
+    let workbook = Workbook::new("../target/test-worksheet-custom_rgb_color.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.set_tab_color(color);
+    workbook.close()
+}
+
+#[test]
+fn test_protect_with_unlocked_cells() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-format-unlocked.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let input = workbook.add_format().set_unlocked();
+    let formula = workbook.add_format().set_hidden();
+    let relocked = workbook.add_format().set_unlocked().set_locked();
+    unsafe {
+        assert_eq!((*input.format).locked, 0);
+        assert_eq!((*formula.format).locked, 1);
+        assert_eq!((*formula.format).hidden, 1);
+        assert_eq!((*relocked.format).locked, 1);
+    }
+    worksheet.write_string(0, 0, "Quantity", None)?;
+    worksheet.write_number(0, 1, 3., Some(&input))?;
+    worksheet.write_formula(1, 1, "=B1*10", Some(&formula))?;
+    worksheet.protect("", &Protection::new())?;
+    workbook.close()
+}
+
+#[test]
+fn test_show_comments_and_author() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-show_comments.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    {
+        let author = String::from("Reviewer");
+        worksheet.set_comments_author(&author)?;
+    }
+    worksheet.write_comment(0, 0, "First comment")?;
+    worksheet.write_comment(2, 1, "Second comment")?;
+    worksheet.show_comments();
+    let err = worksheet.set_comments_author("Bad\0Author").unwrap_err();
+    assert_eq!(err.error, crate::error::STRING_CONTAINS_NUL);
+    workbook.close()
+}
+
+#[test]
+fn test_insert_button() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-insert_button.xlsm");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.insert_button(
+        1,
+        1,
+        &ButtonOptions {
+            caption: Some("Press Me".to_string()),
+            macro_name: Some("say_hello".to_string()),
+            description: Some("Runs the say_hello macro".to_string()),
+            width: 80,
+            height: 30,
+        },
+    )?;
+    worksheet.insert_button(4, 1, &ButtonOptions::default())?;
+    let err = worksheet
+        .insert_button(
+            6,
+            1,
+            &ButtonOptions {
+                macro_name: Some("say\0hello".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::STRING_CONTAINS_NUL);
+    workbook.close()
+}
+
+#[test]
+fn test_add_vba_project() -> Result<(), XlsxError> {
+    // Not a real VBA project, libxlsxwriter only checks that the file can be read.
+    let vba_project = "../target/test-workbook-vbaProject.bin";
+    std::fs::write(vba_project, [0xcc, 0x61, 0xff, 0xff, 0x00, 0x00]).unwrap();
+    let workbook = Workbook::new("../target/test-workbook-add_vba_project.xlsm");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    workbook.add_vba_project(vba_project)?;
+    worksheet.set_vba_name("Sheet1")?;
+    worksheet.insert_button(
+        1,
+        1,
+        &ButtonOptions {
+            macro_name: Some("say_hello".to_string()),
+            ..Default::default()
+        },
+    )?;
+    assert!(workbook
+        .add_vba_project("../target/test-workbook-missing-vbaProject.bin")
+        .is_err());
+    workbook.close()
+}
+
+#[test]
+fn test_add_vba_project_buffer() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-workbook-add_vba_project_buffer.xlsm");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    workbook.add_vba_project_buffer(&[0xcc, 0x61, 0xff, 0xff, 0x00, 0x00])?;
+    worksheet.set_vba_name("Sheet1")?;
+    let path = workbook.vba_project_file.borrow().clone().unwrap();
+    assert_eq!(
+        std::fs::read(&path).unwrap(),
+        [0xcc, 0x61, 0xff, 0xff, 0x00, 0x00]
+    );
+    // Adding another project writes a new file and removes the one it replaces.
+    workbook.add_vba_project_buffer(&[0xcc, 0x61, 0xff, 0xff, 0x01, 0x00])?;
+    let second_path = workbook.vba_project_file.borrow().clone().unwrap();
+    assert_ne!(second_path, path);
+    assert!(!path.exists());
+    assert_eq!(
+        std::fs::read(&second_path).unwrap(),
+        [0xcc, 0x61, 0xff, 0xff, 0x01, 0x00]
+    );
+    workbook.close()?;
+    // The temporary file is only needed until the workbook is written.
+    assert!(!second_path.exists());
+
+    // The file is also removed when closing the workbook fails.
+    let workbook =
+        Workbook::new("../target/missing-directory/test-workbook-add_vba_project_buffer.xlsm");
+    workbook.add_worksheet(None)?;
+    workbook.add_vba_project_buffer(&[0xcc, 0x61, 0xff, 0xff, 0x00, 0x00])?;
+    let path = workbook.vba_project_file.borrow().clone().unwrap();
+    assert!(workbook.close().is_err());
+    assert!(!path.exists());
+    Ok(())
+}
+
+#[test]
+fn test_create_temp_file_does_not_reuse_names() {
+    let first = crate::workbook::create_temp_file("xlsxwriter-test", b"first").unwrap();
+    let second = crate::workbook::create_temp_file("xlsxwriter-test", b"second").unwrap();
+    assert_ne!(first, second);
+    assert_eq!(std::fs::read(&first).unwrap(), b"first");
+    assert_eq!(std::fs::read(&second).unwrap(), b"second");
+    std::fs::remove_file(first).unwrap();
+    std::fs::remove_file(second).unwrap();
+}
+
+#[test]
+fn test_with_c_str() -> Result<(), XlsxError> {
+    let read = |x: *const std::os::raw::c_char| unsafe {
+        std::ffi::CStr::from_ptr(x).to_str().unwrap().to_string()
+    };
+    assert_eq!(crate::with_c_str("short", read)?, "short");
+    let long = "x".repeat(127);
+    assert_eq!(crate::with_c_str(&long, read)?, long);
+    let long = "y".repeat(1000);
+    assert_eq!(crate::with_c_str(&long, read)?, long);
+    for value in ["a\0b".to_string(), format!("{}\0", "z".repeat(200))].iter() {
+        let err = crate::with_c_str(value, read).unwrap_err();
+        assert_eq!(err.error, crate::error::STRING_CONTAINS_NUL);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_default_url_format() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-workbook-url_format.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let url_format = workbook.url_format();
+    assert_eq!(url_format.format, workbook.url_format().format);
+    for row in 0..10 {
+        worksheet.write_url(row, 0, &format!("https://example.com/{}", row), None)?;
+    }
+    worksheet.write_url(10, 0, "https://example.com/", Some(&url_format))?;
+    worksheet.write_string(10, 0, "Example", Some(&url_format))?;
+    workbook.close()
+}
+
+#[test]
+fn test_workbook_comments_author() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-workbook-comments_author.xlsx");
+    let mut worksheet1 = workbook.add_worksheet(None)?;
+    workbook.set_comments_author("Team")?;
+    let mut worksheet2 = workbook.add_worksheet(None)?;
+    worksheet2.set_comments_author("Alice")?;
+    assert_eq!(workbook.worksheets.borrow().len(), 2);
+
+    worksheet1.write_comment(0, 0, "Written by Team")?;
+    worksheet2.write_comment(0, 0, "Written by Alice")?;
+    let author = std::ffi::CString::new("Bob").unwrap();
+    let mut options = CommentOptions {
+        visible: 0,
+        author: author.as_ptr() as *mut _,
+        width: 0,
+        height: 0,
+        x_scale: 0.,
+        y_scale: 0.,
+        color: 0,
+        font_name: std::ptr::null_mut(),
+        font_size: 0.,
+        font_family: 0,
+        start_row: 0,
+        start_col: 0,
+        x_offset: 0,
+        y_offset: 0,
+    };
+    worksheet2.write_comment_opt(1, 0, "Written by Bob", &mut options)?;
+    assert!(workbook.set_comments_author("Bad\0Author").is_err());
+    workbook.close()
+}
+
+#[test]
+fn test_protect_default() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-protect_default.xlsx");
+    let mut worksheet1 = workbook.add_worksheet(None)?;
+    let mut worksheet2 = workbook.add_worksheet(None)?;
+    let input = workbook.add_format().set_unlocked();
+    worksheet1.write_number(0, 0, 1., Some(&input))?;
+    worksheet1.protect_default();
+    worksheet2.protect_with_password("secret")?;
+    let mut worksheet3 = workbook.add_worksheet(None)?;
+    let err = worksheet3.protect_with_password("sec\0ret").unwrap_err();
+    assert_eq!(err.error, crate::error::STRING_CONTAINS_NUL);
+    workbook.close()
+}
+
+#[test]
+fn test_table_structured_formula() -> Result<(), XlsxError> {
+    let column = TableColumn::structured_formula("[@Quantity]*[@Price]");
+    assert_eq!(column.formula.as_deref(), Some("=[@Quantity]*[@Price]"));
+    let column = TableColumn::structured_formula("=SUM([Price])");
+    assert_eq!(column.formula.as_deref(), Some("=SUM([Price])"));
+
+    let workbook = Workbook::new("../target/test-worksheet-table_structured_formula.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for row in 1..4 {
+        worksheet.write_number(row, 0, row.into(), None)?;
+        worksheet.write_number(row, 1, 2.5, None)?;
+    }
+    let columns = |formula: TableColumn<'static>| {
+        vec![
+            TableColumn {
+                header: Some("Quantity".to_string()),
+                ..Default::default()
+            },
+            TableColumn {
+                header: Some("Price".to_string()),
+                ..Default::default()
+            },
+            TableColumn {
+                header: Some("Total".to_string()),
+                ..formula
+            },
+        ]
+    };
+    let err = worksheet
+        .add_table(
+            0,
+            0,
+            3,
+            2,
+            Some(TableOptions {
+                columns: Some(columns(TableColumn::structured_formula(" = "))),
+                ..Default::default()
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::EMPTY_FORMULA);
+    worksheet.add_table(
+        0,
+        0,
+        3,
+        2,
+        Some(TableOptions {
+            columns: Some(columns(TableColumn::structured_formula(
+                "[@Quantity]*[@Price]",
+            ))),
+            ..Default::default()
+        }),
+    )?;
+    workbook.close()
+}
+
+#[test]
+fn test_table_total_conflict() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-table_total_conflict.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let columns = |total_string: Option<&str>| {
+        vec![
+            TableColumn {
+                header: Some("Item".to_string()),
+                total_string: Some("Total".to_string()),
+                ..Default::default()
+            },
+            TableColumn {
+                header: Some("Amount".to_string()),
+                total_string: total_string.map(|x| x.to_string()),
+                total_function: TableTotalFunction::Sum,
+                ..Default::default()
+            },
+        ]
+    };
+    let err = worksheet
+        .add_table(
+            2,
+            1,
+            5,
+            2,
+            Some(TableOptions {
+                total_row: true,
+                columns: Some(columns(Some("Sum"))),
+                ..Default::default()
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::TABLE_TOTAL_CONFLICT);
+    assert_eq!(err.cell(), Some((5, 2)));
+    // The column of the conflict is computed without overflowing at the last column.
+    let err = worksheet
+        .add_table(
+            2,
+            WorksheetCol::MAX - 1,
+            5,
+            WorksheetCol::MAX,
+            Some(TableOptions {
+                total_row: true,
+                columns: Some(columns(Some("Sum"))),
+                ..Default::default()
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::TABLE_TOTAL_CONFLICT);
+    assert_eq!(err.cell(), Some((5, WorksheetCol::MAX)));
+    worksheet.add_table(
+        2,
+        1,
+        5,
+        2,
+        Some(TableOptions {
+            total_row: true,
+            columns: Some(columns(None)),
+            ..Default::default()
+        }),
+    )?;
+    workbook.close()
+}
+
+#[test]
+fn test_set_vba_names() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-workbook-set_vba_name.xlsm");
+    let mut worksheet1 = workbook.add_worksheet(None)?;
+    let mut worksheet2 = workbook.add_worksheet(None)?;
+    workbook.set_vba_name("MyWorkbook")?;
+    worksheet1.set_vba_name("MySheet1")?;
+    worksheet2.set_vba_name("MySheet2")?;
+    assert!(workbook.set_vba_name("My\0Workbook").is_err());
+    workbook.close()
+}
+
+#[test]
+fn test_autofit() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-autofit.xlsx");
+    let mut untracked = workbook.add_worksheet(None)?;
+    untracked.write_string(0, 0, "Not tracked", None)?;
+    assert!(!untracked.autofit_enabled);
+
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.enable_autofit();
+    assert!(worksheet.autofit_enabled);
+    // Enabling autofit for one worksheet doesn't enable it for the others.
+    assert!(!untracked.autofit_enabled);
+    let format = workbook.add_format().set_bold();
+    worksheet.set_column(1, 1, 40., None)?;
+    worksheet.set_column(2, 2, 8.43, Some(&format))?;
+    worksheet.write_string(0, 0, "Short", None)?;
+    worksheet.write_string(1, 0, "A much longer string than the default width", None)?;
+    worksheet.write_string(0, 1, "Fits", None)?;
+    worksheet.write_number(0, 2, -1234567., None)?;
+    worksheet.write_string(0, 3, "ab", None)?;
+    worksheet.autofit()?;
+    assert_eq!(
+        workbook.text_widths.borrow()[&worksheet.worksheet],
+        vec![43, 4, 8, 2]
+    );
+    // Only worksheets that called enable_autofit() are tracked.
+    untracked.write_string(1, 0, "Still not tracked", None)?;
+    assert!(!workbook
+        .text_widths
+        .borrow()
+        .contains_key(&untracked.worksheet));
+    // A worksheet looked up again keeps tracking.
+    assert!(workbook.get_worksheet("Sheet2").unwrap().autofit_enabled);
+    assert!(!workbook.get_worksheet("Sheet1").unwrap().autofit_enabled);
+
+    let width = |col: usize| unsafe { *(*worksheet.worksheet).col_sizes.add(col) };
+    assert!(width(0) > 44.);
+    // Columns are never narrowed and keep their format.
+    assert_eq!(width(1), 40.);
+    assert!(width(2) > 8.43);
+    unsafe {
+        assert_eq!(*(*worksheet.worksheet).col_formats.add(2), format.format);
+    }
+
+    assert_eq!(crate::worksheet::number_width(0.), 1);
+    assert_eq!(crate::worksheet::number_width(-1234567.), 8);
+    assert_eq!(crate::worksheet::number_width(0.1), 11);
+    assert_eq!(crate::worksheet::text_width("two\nlines longer"), 12);
+    workbook.close()
+}
+
+#[test]
+fn test_check_url() -> Result<(), XlsxError> {
+    use crate::worksheet::check_url;
+    for url in [
+        "https://example.com/",
+        "ftp://www.python.org/",
+        "mailto:foo@example.com",
+        "internal:Sheet2!A1",
+        "internal:'Sales Data'!A1:B2",
+        "internal:'Q1!Q2'!A1",
+        "external:c:\\temp\\foo.xlsx",
+        "external:..\\foo.xlsx#Sheet2!A1",
+    ]
+    .iter()
+    {
+        assert!(check_url(url).is_ok(), "{}", url);
+    }
+    for (url, error) in [
+        ("example.com", crate::error::URL_UNKNOWN_SCHEME),
+        ("://example.com", crate::error::URL_UNKNOWN_SCHEME),
+        ("https://", crate::error::URL_MISSING_TARGET),
+        ("mailto:", crate::error::URL_MISSING_TARGET),
+        ("internal:", crate::error::URL_MISSING_TARGET),
+        ("internal:A1", crate::error::URL_MISSING_SHEET),
+        ("internal:!A1", crate::error::URL_MISSING_SHEET),
+        ("internal:Sheet2!", crate::error::URL_MISSING_TARGET),
+        ("external:", crate::error::URL_MISSING_TARGET),
+        ("external:foo.xlsx#", crate::error::URL_MISSING_TARGET),
+    ]
+    .iter()
+    {
+        assert_eq!(check_url(url).unwrap_err().error, *error, "{}", url);
+    }
+
+    let workbook = Workbook::new("../target/test-worksheet-check_url.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    workbook.add_worksheet(None)?;
+    worksheet.write_url(0, 0, "internal:Sheet2!A1", None)?;
+    let err = worksheet.write_url(1, 0, "internal:A1", None).unwrap_err();
+    assert_eq!(err.error, crate::error::URL_MISSING_SHEET);
+    assert_eq!(err.cell(), Some((1, 0)));
+    workbook.close()
+}
+
+#[test]
+fn test_set_print_options() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-set_print_options.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_string(0, 0, "Print options", None)?;
+    worksheet.set_print_options(&PrintOptions {
+        center_horizontally: true,
+        center_vertically: true,
+        print_row_col_headers: true,
+        gridlines: Some(GridLines::ShowAllGridLines),
+    });
+    unsafe {
+        let raw = &*worksheet.worksheet;
+        assert_eq!(raw.hcenter, 1);
+        assert_eq!(raw.vcenter, 1);
+        assert_eq!(raw.print_headers, 1);
+        assert_eq!(raw.print_gridlines, 1);
+        assert_eq!(raw.screen_gridlines, 1);
+    }
+
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.set_print_options(&PrintOptions::default());
+    unsafe {
+        let raw = &*worksheet.worksheet;
+        assert_eq!(raw.hcenter, 0);
+        assert_eq!(raw.print_headers, 0);
+        assert_eq!(raw.print_gridlines, 0);
+    }
+    workbook.close()
+}
+
+#[test]
+fn test_paper_type_code() -> Result<(), XlsxError> {
+    assert_eq!(PaperType::A4.code(), 9);
+    assert_eq!(PaperType::from_code(PaperType::A4.code()), PaperType::A4);
+    for code in 0..=u8::MAX {
+        assert_eq!(PaperType::from_code(code).code(), code);
+    }
+    assert_eq!(PaperType::from_code(42), PaperType::Other(42));
+
+    let workbook = Workbook::new("../target/test-worksheet-paper_type_code.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.set_paper(PaperType::EnvelopeDL);
+    unsafe {
+        assert_eq!((*worksheet.worksheet).paper_size, 27);
+    }
+    workbook.close()
+}
+
+#[test]
+fn test_add_table_simple() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-add_table_simple.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_string(0, 0, "Region", None)?;
+    worksheet.write_string(0, 1, "Sales", None)?;
+    worksheet.write_string(1, 0, "North", None)?;
+    worksheet.write_number(1, 1, 1250., None)?;
+
+    let options = TableOptions::styled(TableStyleType::Medium, 9).highlight_first_column();
+    assert_eq!(options.style_type, TableStyleType::Medium);
+    assert_eq!(options.style_type_number, 9);
+    assert!(options.first_column);
+    assert!(!options.last_column);
+    assert!(!options.no_header_row);
+    worksheet.add_table(0, 0, 1, 1, Some(options))?;
+
+    worksheet.add_table_simple(3, 0, 4, 1, TableStyleType::Light, 11)?;
+    let err = worksheet
+        .add_table_simple(6, 0, 7, 1, TableStyleType::Dark, 12)
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::TABLE_STYLE_NUMBER_OUT_OF_RANGE);
+    workbook.close()
+}
+
+#[test]
+fn test_table_options_clone_without_formats() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-table_options_clone.xlsx");
+    let bold = workbook.add_format().set_bold();
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let base = TableOptions {
+        total_row: true,
+        columns: Some(vec![
+            TableColumn {
+                header: Some("Item".to_string()),
+                total_string: Some("Total".to_string()),
+                header_format: Some(bold),
+                ..Default::default()
+            },
+            TableColumn {
+                header: Some("Amount".to_string()),
+                total_function: TableTotalFunction::Sum,
+                ..Default::default()
+            },
+        ]),
+        ..TableOptions::styled(TableStyleType::Medium, 2).highlight_first_column()
+    };
+
+    let first = base.clone_without_formats();
+    assert_eq!(first.style_type, TableStyleType::Medium);
+    assert_eq!(first.style_type_number, 2);
+    assert!(first.first_column && first.total_row);
+    let columns = first.columns.as_ref().unwrap();
+    assert_eq!(columns[0].header.as_deref(), Some("Item"));
+    assert_eq!(columns[0].total_string.as_deref(), Some("Total"));
+    assert!(columns[0].header_format.is_none());
+    assert_eq!(columns[1].total_function, TableTotalFunction::Sum);
+    assert!(base.columns.as_ref().unwrap()[0].header_format.is_some());
+
+    worksheet.add_table(0, 0, 3, 1, Some(first))?;
+    worksheet.add_table(5, 0, 8, 1, Some(base.clone_without_formats()))?;
+    worksheet.add_table(10, 0, 13, 1, Some(base))?;
+    workbook.close()
+}
+
+#[test]
+fn test_set_row_range() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-set_row_range.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let format = workbook.add_format().set_bg_color(FormatColor::Silver);
+    worksheet.set_row_range(1, 100, 30., Some(&format))?;
+    worksheet.set_row_range(101, 101, 10., None)?;
+    for row in 1..=101 {
+        worksheet.write_number(row, 0, row.into(), None)?;
+    }
+
+    let err = worksheet.set_row_range(5, 4, 30., None).unwrap_err();
+    assert_eq!(err.error, crate::error::REVERSED_RANGE);
+    let err = worksheet
+        .set_row_range(1_048_574, 1_048_576, 30., None)
+        .unwrap_err();
+    assert_eq!(
+        err.error,
+        libxlsxwriter_sys::lxw_error_LXW_ERROR_WORKSHEET_INDEX_OUT_OF_RANGE
+    );
+    workbook.close()
+}
+
+#[test]
+fn test_insert_image_dimensions() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-insert_image_dimensions.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let size = worksheet.insert_image(0, 0, "../images/simple1.png")?;
+    assert_eq!(
+        size,
+        Some(ImageDimensions {
+            width: 425,
+            height: 165
+        })
+    );
+    // Fill bytes before a marker are skipped when the JPEG header is parsed.
+    let jpeg = [
+        0xff, 0xd8, 0xff, 0xff, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x00, 0x20, 0x00, 0x40,
+    ];
+    assert_eq!(image_dimensions(&jpeg), Some((64, 32)));
+    // Segments before the frame header, such as a large EXIF block, are skipped without reading them.
+    let mut exif_jpeg = vec![0xff, 0xd8, 0xff, 0xe1, 0xff, 0xfe];
+    exif_jpeg.resize(exif_jpeg.len() + 0xfffc, 0);
+    exif_jpeg.extend_from_slice(&jpeg[4..]);
+    let exif_jpeg_path = "../target/test-worksheet-insert_image_dimensions-exif.jpg";
+    std::fs::write(exif_jpeg_path, &exif_jpeg).unwrap();
+    assert_eq!(
+        crate::image::image_file_dimensions(exif_jpeg_path).unwrap(),
+        Some((64, 32))
+    );
+    assert_eq!(image_dimensions(&exif_jpeg[..100]), None);
+    assert!(crate::image::image_file_dimensions("../images/does-not-exist.png").is_err());
+    assert!(worksheet
+        .insert_image(10, 0, "../images/does-not-exist.png")
+        .is_err());
+    workbook.close()
+}
+
+#[test]
+fn test_conditional_format_multi_range_cells() -> Result<(), XlsxError> {
+    use crate::conditional_formatting::cell_name;
+    assert_eq!(cell_name(0, 0)?, "A1");
+    assert_eq!(cell_name(9, 25)?, "Z10");
+    assert_eq!(cell_name(0, 26)?, "AA1");
+    assert_eq!(cell_name(1_048_575, 16_383)?, "XFD1048576");
+    assert_eq!(cell_name(u32::MAX - 1, 0)?, "A4294967295");
+    assert_eq!(
+        cell_name(u32::MAX, 0).unwrap_err().error,
+        libxlsxwriter_sys::lxw_error_LXW_ERROR_WORKSHEET_INDEX_OUT_OF_RANGE
+    );
+    assert!(ConditionalFormat::new_typed(ConditionalType::Cell)
+        .set_multi_range_cells(&[(0, 0, u32::MAX, 0)])
+        .is_err());
+
+    let workbook = Workbook::new("../target/test-conditional_format_multi_range_cells.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    for i in 0..5 {
+        worksheet.write_number(i, 0, i.into(), None)?;
+        worksheet.write_number(i, 2, (i * 3).into(), None)?;
+    }
+    let mut conditional_format = ConditionalFormat::new(workbook.add_format().set_bold())
+        .set_conditional_type(ConditionalType::Cell)
+        .set_criteria(ConditionalCriteria::GreaterThan)
+        .set_value(5.)
+        .set_stop_if_true(true)
+        .set_multi_range_cells(&[(0, 0, 4, 0), (0, 2, 4, 2), (6, 1, 6, 1)])?;
+    assert_eq!(conditional_format._internal_format.stop_if_true, 1);
+    worksheet.conditional_format_range(0, 0, 4, 0, &mut conditional_format)?;
+    // The strings are passed to libxlsxwriter when the rule is added to the worksheet.
+    let multi_range =
+        unsafe { std::ffi::CStr::from_ptr(conditional_format._internal_format.multi_range) };
+    assert_eq!(multi_range.to_str().unwrap(), "A1:A5 C1:C5 B7");
+    workbook.close()
+}
+
+#[test]
+fn test_insert_image_url_and_tip() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-insert_image_url_and_tip.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let data = include_bytes!("../../images/simple1.png");
+    worksheet.insert_image_buffer_opt(
+        0,
+        0,
+        &data[..],
+        &ImageOptions {
+            url: Some("https://github.com/informationsea/xlsxwriter-rs".to_string()),
+            tip: Some("Project page".to_string()),
+            ..Default::default()
+        },
+    )?;
+    worksheet.insert_image_opt(
+        10,
+        0,
+        "../images/simple1.png",
+        &ImageOptions {
+            x_scale: 0.5,
+            y_scale: 0.5,
+            url: Some("internal:Sheet1!A1".to_string()),
+            ..Default::default()
+        },
+    )?;
+
+    let err = worksheet
+        .insert_image_buffer_opt(
+            20,
+            0,
+            &data[..],
+            &ImageOptions {
+                url: Some("github.com".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::URL_UNKNOWN_SCHEME);
+    assert_eq!(err.cell(), Some((20, 0)));
+    let err = worksheet
+        .insert_image_buffer_opt(
+            20,
+            0,
+            &data[..],
+            &ImageOptions {
+                tip: Some("a\0b".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::STRING_CONTAINS_NUL);
+    workbook.close()
+}
+
+#[test]
+fn test_format_color_from_hex() -> Result<(), XlsxError> {
+    assert_eq!(
+        FormatColor::from_hex("#1A2B3C")?,
+        FormatColor::Custom(0x1A2B3C)
+    );
+    assert_eq!(
+        FormatColor::from_hex("1a2b3c")?,
+        FormatColor::Custom(0x1A2B3C)
+    );
+    assert_eq!(
+        FormatColor::from_hex("#000000")?.value(),
+        FormatColor::Black.value()
+    );
+    assert_eq!(
+        FormatColor::from_hex_u32(0xFFFFFF)?,
+        FormatColor::Custom(0xFFFFFF)
+    );
+    for hex in [
+        "", "#", "#12345", "#1234567", "##1A2B3C", "#1A2B3G", "+1A2B3C", "#1A 2B3",
+    ]
+    .iter()
+    {
+        let err = FormatColor::from_hex(hex).unwrap_err();
+        assert_eq!(err.error, crate::error::INVALID_HEX_COLOR, "{}", hex);
+    }
+    assert_eq!(
+        FormatColor::from_hex_u32(0x1000000).unwrap_err().error,
+        crate::error::INVALID_HEX_COLOR
+    );
+
+    let workbook = Workbook::new("../target/test-format-color_from_hex.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let format = workbook
+        .add_format()
+        .set_font_color(FormatColor::from_hex("#9C0006")?)
+        .set_bg_color(FormatColor::from_hex("#FFC7CE")?);
+    worksheet.write_string(0, 0, "Themed", Some(&format))?;
+    workbook.close()
+}
+
+#[test]
+fn test_freeze_panes_opt() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-freeze_panes_opt.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_string(0, 0, "Header", None)?;
+    for row in 1..=100 {
+        worksheet.write_number(row, 0, row.into(), None)?;
+    }
+    worksheet.freeze_panes_opt(1, 0, 50, 0, FreezePaneType::Frozen);
+    unsafe {
+        let panes = (*worksheet.worksheet).panes;
+        assert_eq!((panes.first_row, panes.first_col), (1, 0));
+        assert_eq!((panes.top_row, panes.left_col), (50, 0));
+    }
+
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.freeze_panes(1, 0);
+    let frozen = unsafe { (*worksheet.worksheet).panes.type_ };
+    worksheet.freeze_panes_opt(1, 1, 20, 5, FreezePaneType::FrozenSplit);
+    unsafe {
+        let panes = (*worksheet.worksheet).panes;
+        assert_ne!(panes.type_, frozen);
+        assert_eq!((panes.top_row, panes.left_col), (20, 5));
+    }
+    workbook.close()
+}
+
+#[test]
+fn test_set_selection_active() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-set_selection_active.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.write_string(9, 3, "Input", None)?;
+    let err = worksheet
+        .set_selection_active(1, 1, 9, 3, 5, 3)
+        .unwrap_err();
+    assert_eq!(
+        err.error,
+        libxlsxwriter_sys::lxw_error_LXW_ERROR_PARAMETER_VALIDATION
+    );
+    let err = worksheet
+        .set_selection_active(9, 3, 1, 1, 9, 3)
+        .unwrap_err();
+    assert_eq!(err.error, crate::error::REVERSED_RANGE);
+    worksheet.set_selection_active(1, 1, 9, 3, 9, 3)?;
+    // libxlsxwriter only keeps one selection per worksheet.
+    assert!(worksheet.set_selection(0, 0, 2, 2).is_err());
+
+    let mut worksheet = workbook.add_worksheet(None)?;
+    assert_eq!(
+        worksheet.set_selection(3, 0, 1, 0).unwrap_err().error,
+        crate::error::REVERSED_RANGE
+    );
+    worksheet.set_selection(1, 0, 3, 0)?;
+
+    let mut worksheet = workbook.add_worksheet(None)?;
+    worksheet.set_selection_active(4, 2, 4, 2, 4, 2)?;
+    workbook.close()
+}
+
+#[test]
+fn test_write_date_and_time() -> Result<(), XlsxError> {
+    let serial = |datetime: &DateTime| unsafe {
+        libxlsxwriter_sys::lxw_datetime_to_excel_datetime(&mut datetime.into())
+    };
+    assert_eq!(serial(&DateTime::from_date(2013, 2, 28)), 41333.);
+    assert_eq!(serial(&DateTime::from_time(12, 0, 0.)), 0.5);
+    assert_eq!(serial(&DateTime::from_time(18, 0, 0.)), 0.75);
+
+    let workbook = Workbook::new("../target/test-worksheet-write_date_and_time.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let date_format = workbook.add_format().set_num_format("yyyy-mm-dd")?;
+    let time_format = workbook.add_format().set_num_format("hh:mm:ss")?;
+    let datetime = DateTime::new(2013, 2, 28, 18, 0, 0.);
+    worksheet.write_date(0, 0, &datetime, Some(&date_format))?;
+    worksheet.write_time(0, 1, &datetime, Some(&time_format))?;
+    worksheet.write_date(1, 0, &DateTime::from_date(2020, 2, 29), Some(&date_format))?;
+    worksheet.write_time(1, 1, &DateTime::from_time(9, 15, 30.), Some(&time_format))?;
+    workbook.close()
+}
+
+#[test]
+fn test_datetime_try_new() -> Result<(), XlsxError> {
+    assert_eq!(
+        DateTime::try_new(2013, 2, 28, 12, 0, 0.)?,
+        DateTime::new(2013, 2, 28, 12, 0, 0.)
+    );
+    DateTime::try_new(2020, 2, 29, 0, 0, 0.)?;
+    DateTime::try_new(2000, 2, 29, 0, 0, 0.)?;
+    DateTime::try_new(2016, 12, 31, 23, 59, 60.5)?;
+    DateTime::try_new(1900, 1, 1, 0, 0, 0.)?;
+    DateTime::try_new(0, 0, 0, 18, 30, 0.)?;
+
+    for (year, month, day) in [
+        (2021, 13, 1),
+        (2021, 0, 1),
+        (2021, -1, 1),
+        (2021, 1, 0),
+        (2021, 1, 32),
+        (2021, 4, 31),
+        (2021, 2, 29),
+        (1900, 2, 29),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            DateTime::try_new(*year, *month, *day, 0, 0, 0.)
+                .unwrap_err()
+                .error,
+            crate::error::INVALID_DATETIME,
+            "{}-{}-{}",
+            year,
+            month,
+            day
+        );
+    }
+    for (hour, min, second) in [
+        (24, 0, 0.),
+        (-1, 0, 0.),
+        (0, 60, 0.),
+        (0, 0, 61.),
+        (0, 0, -0.5),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            DateTime::try_new(2021, 1, 1, *hour, *min, *second)
+                .unwrap_err()
+                .error,
+            crate::error::INVALID_DATETIME
+        );
+    }
+    assert!(DateTime::try_new(2021, 1, 1, 0, 0, f64::NAN).is_err());
+    for year in [1899, 10000, -1].iter() {
+        assert_eq!(
+            DateTime::try_new(*year, 1, 1, 0, 0, 0.).unwrap_err().error,
+            crate::error::DATETIME_OUT_OF_RANGE
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_datetime_to_serial() -> Result<(), XlsxError> {
+    // Serial numbers as shown by Excel for the cells formatted as General. Excel can't show dates before 1904 in the 1904 epoch,
+    // they count the days backwards from 1904-01-01.
+    for (datetime, serial_1900, serial_1904) in [
+        (DateTime::from_date(1900, 1, 1), 1., -1460.),
+        (DateTime::from_date(1900, 2, 28), 59., -1402.),
+        (DateTime::from_date(1900, 3, 1), 61., -1401.),
+        (DateTime::from_date(1904, 1, 1), 1462., 0.),
+        (DateTime::from_date(1970, 1, 1), 25569., 24107.),
+        (DateTime::new(2013, 2, 28, 12, 0, 0.), 41333.5, 39871.5),
+        (DateTime::new(2020, 2, 29, 18, 0, 0.), 43890.75, 42428.75),
+        (DateTime::from_date(9999, 12, 31), 2958465., 2957003.),
+        (DateTime::from_time(6, 0, 0.), 0.25, 0.25),
+    ]
+    .iter()
+    {
+        assert_eq!(datetime.to_serial(false), *serial_1900, "{:?}", datetime);
+        assert_eq!(datetime.to_serial(true), *serial_1904, "{:?}", datetime);
+    }
+
+    // The same as the value libxlsxwriter writes.
+    for year in [1900, 1901, 1999, 2000, 2024, 2100].iter() {
+        for month in 1..=12 {
+            let datetime = DateTime::new(*year, month, 28, 23, 59, 59.5);
+            let expected = unsafe {
+                libxlsxwriter_sys::lxw_datetime_to_excel_datetime(&mut (&datetime).into())
+            };
+            assert!((datetime.to_serial(false) - expected).abs() < 1e-9);
+        }
+    }
+
+    let mut dates = std::collections::HashSet::new();
+    dates.insert(DateTime::from_date(2013, 2, 28));
+    dates.insert(DateTime::new(2013, 2, 28, 0, 0, -0.));
+    dates.insert(DateTime::from_time(12, 0, 0.));
+    assert_eq!(dates.len(), 2);
+    let nan = DateTime::new(2013, 2, 28, 0, 0, f64::NAN);
+    assert_eq!(nan, nan.clone());
+    dates.insert(nan.clone());
+    assert!(dates.contains(&nan));
+    assert_eq!(dates.len(), 3);
+
+    // Ordering agrees with equality, also for NaN and -0.0 seconds.
+    use std::cmp::Ordering;
+    let zero = DateTime::new(2013, 2, 28, 0, 0, 0.);
+    let negative_zero = DateTime::new(2013, 2, 28, 0, 0, -0.);
+    let one = DateTime::new(2013, 2, 28, 0, 0, 1.);
+    assert_eq!(zero, negative_zero);
+    assert_eq!(zero.partial_cmp(&negative_zero), Some(Ordering::Equal));
+    assert_eq!(nan.partial_cmp(&nan.clone()), Some(Ordering::Equal));
+    assert_eq!(nan.cmp(&one), Ordering::Greater);
+    assert_eq!(negative_zero.cmp(&one), Ordering::Less);
+    assert!(DateTime::from_date(2013, 3, 1) > nan);
+    let mut sorted = vec![
+        nan.clone(),
+        one.clone(),
+        negative_zero.clone(),
+        zero.clone(),
+    ];
+    sorted.sort();
+    assert_eq!(
+        sorted,
+        vec![zero.clone(), zero.clone(), one.clone(), nan.clone()]
+    );
+    let tree: std::collections::BTreeSet<_> = sorted.into_iter().collect();
+    assert_eq!(tree.len(), 3);
+    assert!(tree.contains(&negative_zero));
+    assert!(tree.contains(&nan));
+    Ok(())
+}
+
+#[test]
+fn test_write_number_lossy() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("../target/test-worksheet-write_number_lossy.xlsx");
+    let mut worksheet = workbook.add_worksheet(None)?;
+    let format = workbook.add_format().set_num_format("0.00")?;
+    let numbers = [1.5, f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -0.];
+    for (row, number) in numbers.iter().enumerate() {
+        worksheet.write_number_lossy(row as WorksheetRow, 0, *number, Some(&format))?;
+        // Every value was written, as a number or as a string.
+        assert!(worksheet.try_write(row as WorksheetRow, 0, CellValue::Blank, None)?);
+    }
+    // The strict path still rejects the same values.
+    for (row, number) in numbers.iter().enumerate().skip(1).take(3) {
+        let err = worksheet
+            .write_number(row as WorksheetRow, 1, *number, None)
+            .unwrap_err();
+        assert_eq!(err.error, crate::error::NON_FINITE_NUMBER);
+    }
+    workbook.close()
+}
@@ -1,5 +1,4 @@
-use super::{convert_bool, DateTime};
-use std::ffi::CString;
+use super::{convert_bool, to_c_string, DateTime, XlsxError};
 use std::os::raw::c_char;
 
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord)]
@@ -182,13 +181,10 @@ pub struct DataValidation {
     pub error_message: Option<String>,
 }
 
-fn option_str_to_cstr_bytes(s: &Option<String>) -> Option<Vec<u8>> {
-    s.as_ref().map(|x| {
-        CString::new(x as &str)
-            .unwrap()
-            .into_bytes_with_nul()
-            .to_vec()
-    })
+fn option_str_to_cstr_bytes(s: &Option<String>) -> Result<Option<Vec<u8>>, XlsxError> {
+    s.as_ref()
+        .map(|x| to_c_string(x).map(|y| y.into_bytes_with_nul()))
+        .transpose()
 }
 
 impl DataValidation {
@@ -242,18 +238,152 @@ impl DataValidation {
             error_message: None,
         }
     }
-    pub(crate) fn to_c_struct(&self) -> CDataValidation {
-        let mut _value_formula = option_str_to_cstr_bytes(&self.value_formula);
-        let mut _value_list: Option<Vec<Vec<u8>>> = self.value_list.as_ref().map(|x| {
-            x.iter()
-                .map(|y| {
-                    CString::new(y as &str)
-                        .unwrap()
-                        .into_bytes_with_nul()
-                        .to_vec()
-                })
-                .collect()
-        });
+
+    /// Create a list validation with an in-cell dropdown showing `values`. Note, the string list is restricted by Excel to 255 characters, including comma separators.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-validation-list-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let validation = DataValidation::list(&["open", "high", "close"]);
+    /// worksheet.data_validation_cell(0, 0, &validation)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn list(values: &[&str]) -> DataValidation {
+        let mut validation = DataValidation::new(
+            DataValidationType::List,
+            DataValidationCriteria::Between,
+            DataValidationErrorType::Stop,
+        );
+        validation.value_list = Some(values.iter().map(|x| x.to_string()).collect());
+        validation.dropdown = true;
+        validation
+    }
+
+    /// Create a list validation with an in-cell dropdown showing the values of a worksheet range such as `=Sheet2!$A$1:$A$20`. Unlike [`DataValidation::list()`], the list isn't limited to 255 characters.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-validation-list_from_formula-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let validation = DataValidation::list_from_formula("=$E$1:$E$10");
+    /// worksheet.data_validation_cell(0, 0, &validation)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn list_from_formula(range: &str) -> DataValidation {
+        let mut validation = DataValidation::new(
+            DataValidationType::ListFormula,
+            DataValidationCriteria::Between,
+            DataValidationErrorType::Stop,
+        );
+        validation.value_formula = Some(range.to_string());
+        validation.dropdown = true;
+        validation
+    }
+
+    /// Create a validation restricting the cell to dates between `start` and `end`, inclusive.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-validation-date_between-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let validation = DataValidation::date_between(
+    ///     &DateTime::new(2024, 1, 1, 0, 0, 0.),
+    ///     &DateTime::new(2024, 12, 31, 0, 0, 0.),
+    /// );
+    /// worksheet.data_validation_cell(0, 0, &validation)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn date_between(start: &DateTime, end: &DateTime) -> DataValidation {
+        let mut validation = DataValidation::new(
+            DataValidationType::Date,
+            DataValidationCriteria::Between,
+            DataValidationErrorType::Stop,
+        );
+        validation.minimum_datetime = start.clone();
+        validation.maximum_datetime = end.clone();
+        validation
+    }
+
+    /// Create a validation restricting the cell to times between `start` and `end`, inclusive. Only the `hour`, `min` and `second` fields of the `DateTime` are used.
+    pub fn time_between(start: &DateTime, end: &DateTime) -> DataValidation {
+        let mut validation = DataValidation::new(
+            DataValidationType::Time,
+            DataValidationCriteria::Between,
+            DataValidationErrorType::Stop,
+        );
+        validation.minimum_datetime = start.clone();
+        validation.maximum_datetime = end.clone();
+        validation
+    }
+
+    /// Restrict the cell to values between `minimum` and `maximum`, inclusive. This sets the criteria to `Between`.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-validation-between-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let validation = DataValidation::new(
+    ///     DataValidationType::Integer,
+    ///     DataValidationCriteria::Between,
+    ///     DataValidationErrorType::Stop,
+    /// )
+    /// .between(1., 10.)
+    /// .input_message("Quantity", "Enter an integer from 1 to 10")
+    /// .error_message("Invalid quantity", "The quantity must be an integer from 1 to 10");
+    /// worksheet.data_validation_cell(0, 0, &validation)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn between(mut self, minimum: f64, maximum: f64) -> DataValidation {
+        self.criteria = DataValidationCriteria::Between;
+        self.minimum_number = minimum;
+        self.maximum_number = maximum;
+        self
+    }
+
+    /// Set the limiting value to which a single value criteria such as `GreaterThan` is applied.
+    pub fn value(mut self, value: f64) -> DataValidation {
+        self.value_number = value;
+        self
+    }
+
+    /// Set the title and the message displayed when the cell is selected. This also turns on `show_input`.
+    pub fn input_message(mut self, title: &str, message: &str) -> DataValidation {
+        self.input_title = Some(title.to_string());
+        self.input_message = Some(message.to_string());
+        self.show_input = true;
+        self
+    }
+
+    /// Set the title and the message of the error dialog displayed when invalid data is entered. This also turns on `show_error`.
+    pub fn error_message(mut self, title: &str, message: &str) -> DataValidation {
+        self.error_title = Some(title.to_string());
+        self.error_message = Some(message.to_string());
+        self.show_error = true;
+        self
+    }
+
+    /// Set the type of error dialog displayed when invalid data is entered.
+    pub fn error_type(mut self, error_type: DataValidationErrorType) -> DataValidation {
+        self.error_type = error_type;
+        self
+    }
+
+    pub(crate) fn to_c_struct(&self) -> Result<CDataValidation, XlsxError> {
+        let mut _value_formula = option_str_to_cstr_bytes(&self.value_formula)?;
+        let mut _value_list: Option<Vec<Vec<u8>>> = self
+            .value_list
+            .as_ref()
+            .map(|x| {
+                x.iter()
+                    .map(|y| to_c_string(y).map(|z| z.into_bytes_with_nul()))
+                    .collect()
+            })
+            .transpose()?;
         let mut _value_list_ptr: Option<Vec<*mut c_char>> = _value_list.as_mut().map(|x| {
             x.iter_mut()
                 .map(|y| y.as_mut_ptr() as *mut c_char)
@@ -262,14 +392,14 @@ impl DataValidation {
         if let Some(l) = _value_list_ptr.as_mut() {
             l.push(std::ptr::null_mut());
         }
-        let mut _minimum_formula = option_str_to_cstr_bytes(&self.minimum_formula);
-        let mut _maximum_formula = option_str_to_cstr_bytes(&self.maximum_formula);
-        let mut _input_title = option_str_to_cstr_bytes(&self.input_title);
-        let mut _input_message = option_str_to_cstr_bytes(&self.input_message);
-        let mut _error_title = option_str_to_cstr_bytes(&self.error_title);
-        let mut _error_message = option_str_to_cstr_bytes(&self.error_message);
+        let mut _minimum_formula = option_str_to_cstr_bytes(&self.minimum_formula)?;
+        let mut _maximum_formula = option_str_to_cstr_bytes(&self.maximum_formula)?;
+        let mut _input_title = option_str_to_cstr_bytes(&self.input_title)?;
+        let mut _input_message = option_str_to_cstr_bytes(&self.input_message)?;
+        let mut _error_title = option_str_to_cstr_bytes(&self.error_title)?;
+        let mut _error_message = option_str_to_cstr_bytes(&self.error_message)?;
 
-        CDataValidation {
+        Ok(CDataValidation {
             data_validation: libxlsxwriter_sys::lxw_data_validation {
                 validate: self.validate.value(),
                 criteria: self.criteria.value(),
@@ -329,7 +459,7 @@ impl DataValidation {
             _input_message,
             _error_title,
             _error_message,
-        }
+        })
     }
 }
 
@@ -1,8 +1,14 @@
-use super::{error, Chart, ChartType, Format, Worksheet, XlsxError};
-use std::cell::RefCell;
+use super::{error, to_c_string, Chart, ChartType, Chartsheet, Format, Worksheet, XlsxError};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::ffi::CString;
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::os::raw::c_char;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// The Workbook is the main object exposed by the libxlsxwriter library. It represents the entire spreadsheet as you see it in Excel and internally it represents the Excel file as it is written on disk.
 ///
@@ -17,34 +23,47 @@ use std::rc::Rc;
 /// ```
 #[derive(Debug)]
 pub struct Workbook {
-    workbook: *mut libxlsxwriter_sys::lxw_workbook,
+    pub(crate) workbook: *mut libxlsxwriter_sys::lxw_workbook,
     _workbook_name: CString,
     pub(crate) const_str: Rc<RefCell<Vec<Vec<u8>>>>,
+    pub(crate) worksheets: RefCell<Vec<*mut libxlsxwriter_sys::lxw_worksheet>>,
+    comments_author: RefCell<Option<CString>>,
+    pub(crate) text_widths: RefCell<HashMap<*mut libxlsxwriter_sys::lxw_worksheet, Vec<usize>>>,
+    pub(crate) non_finite_as_blank: Cell<bool>,
+    pub(crate) vba_project_file: RefCell<Option<PathBuf>>,
 }
 
 impl Workbook {
     /// This function is used to create a new Excel workbook with a given filename.
     /// When specifying a filename it is recommended that you use an .xlsx extension or Excel will generate a warning when opening the file.
+    ///
+    /// # Panics
+    /// Panics if `filename` contains a NUL character. Use [`Workbook::try_new()`] to get an error instead.
     pub fn new(filename: &str) -> Workbook {
-        unsafe {
-            let workbook_name = CString::new(filename).expect("Null Error");
-            let raw_workbook = libxlsxwriter_sys::workbook_new(workbook_name.as_c_str().as_ptr());
-            if raw_workbook.is_null() {
-                unreachable!()
-            }
-            Workbook {
-                workbook: raw_workbook,
-                _workbook_name: workbook_name,
-                const_str: Rc::new(RefCell::new(Vec::new())),
-            }
-        }
+        Workbook::try_new(filename).expect("workbook filename contains a NUL character")
+    }
+
+    /// This function is the same as [`Workbook::new()`] but returns an error instead of panicking if `filename` contains a NUL
+    /// character:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// let workbook = Workbook::try_new("test-workbook-try_new-1.xlsx")?;
+    /// assert!(Workbook::try_new("test-workbook\0-try_new-2.xlsx").is_err());
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn try_new(filename: &str) -> Result<Workbook, XlsxError> {
+        let workbook_name = to_c_string(filename)?;
+        let raw_workbook = unsafe { libxlsxwriter_sys::workbook_new(workbook_name.as_ptr()) };
+        Workbook::from_raw(raw_workbook, workbook_name)
     }
 
     /// This function is the same as the [`Workbook::new()`] constructor but allows additional options to be set.
     /// ```rust
     /// # use xlsxwriter::*;
     /// # fn main() -> Result<(), XlsxError> {
-    /// let workbook = Workbook::new_with_options("test-workbook_with_options.xlsx", true, Some("target"), true);
+    /// let workbook = Workbook::new_with_options("test-workbook_with_options.xlsx", true, Some("target"), true)?;
     /// let mut worksheet = workbook.add_worksheet(None)?;
     /// worksheet.write_string(0, 0, "Hello Excel", None)?;
     /// workbook.close()
@@ -72,13 +91,18 @@ impl Workbook {
     /// memory remains constant. In these cases you should use an alternative temp file location by using the `tmpdir` option shown
     /// above. See [Constant memory mode and the /tmp directory](https://libxlsxwriter.github.io/working_with_memory.html#ww_mem_temp)
     /// for more details.
+    ///
+    /// A `filename` or `tmpdir` with an interior NUL character returns an error.
     pub fn new_with_options(
         filename: &str,
         constant_memory: bool,
         tmpdir: Option<&str>,
         use_zip64: bool,
-    ) -> Workbook {
-        let tmpdir_vec = tmpdir.map(|x| CString::new(x).unwrap().as_bytes_with_nul().to_vec());
+    ) -> Result<Workbook, XlsxError> {
+        let workbook_name = to_c_string(filename)?;
+        let tmpdir_vec = tmpdir
+            .map(|x| to_c_string(x).map(|y| y.into_bytes_with_nul()))
+            .transpose()?;
 
         unsafe {
             let tmpdir_ptr = if let Some(tmpdir) = tmpdir_vec.as_ref() {
@@ -93,27 +117,40 @@ impl Workbook {
                 use_zip64: use_zip64 as u8,
             };
 
-            let workbook_name = CString::new(filename).expect("Null Error");
+            let raw_workbook =
+                libxlsxwriter_sys::workbook_new_opt(workbook_name.as_ptr(), &mut workbook_options);
+            Workbook::from_raw(raw_workbook, workbook_name)
+        }
+    }
 
-            let raw_workbook = libxlsxwriter_sys::workbook_new_opt(
-                workbook_name.as_c_str().as_ptr(),
-                &mut workbook_options,
-            );
-            if raw_workbook.is_null() {
-                unreachable!()
-            }
-            Workbook {
-                workbook: raw_workbook,
-                _workbook_name: workbook_name,
-                const_str: Rc::new(RefCell::new(Vec::new())),
-            }
+    fn from_raw(
+        raw_workbook: *mut libxlsxwriter_sys::lxw_workbook,
+        workbook_name: CString,
+    ) -> Result<Workbook, XlsxError> {
+        if raw_workbook.is_null() {
+            return Err(XlsxError::new(
+                libxlsxwriter_sys::lxw_error_LXW_ERROR_MEMORY_MALLOC_FAILED,
+            ));
         }
+        Ok(Workbook {
+            workbook: raw_workbook,
+            _workbook_name: workbook_name,
+            const_str: Rc::new(RefCell::new(Vec::new())),
+            worksheets: RefCell::new(Vec::new()),
+            comments_author: RefCell::new(None),
+            text_widths: RefCell::new(HashMap::new()),
+            non_finite_as_blank: Cell::new(false),
+            vba_project_file: RefCell::new(None),
+        })
     }
+
     pub fn add_worksheet<'a>(
         &'a self,
         sheet_name: Option<&str>,
     ) -> Result<Worksheet<'a>, XlsxError> {
-        let name_vec = sheet_name.map(|x| CString::new(x).unwrap().as_bytes_with_nul().to_vec());
+        let name_vec = sheet_name
+            .map(|x| to_c_string(x).map(|y| y.into_bytes_with_nul()))
+            .transpose()?;
         unsafe {
             if let Some(sheet_name) = name_vec.as_ref() {
                 let result = libxlsxwriter_sys::workbook_validate_sheet_name(
@@ -141,9 +178,76 @@ impl Workbook {
                 return Err(XlsxError::new(error::UNKNOWN_ERROR_CODE));
             }
 
+            if let Some(author) = self.comments_author.borrow().as_ref() {
+                libxlsxwriter_sys::worksheet_set_comments_author(worksheet, author.as_ptr());
+            }
+            self.worksheets.borrow_mut().push(worksheet);
+
             Ok(Worksheet {
                 _workbook: self,
                 worksheet,
+                autofit_enabled: false,
+            })
+        }
+    }
+
+    /// This function is used to add a chartsheet to a workbook. A chartsheet is a special type of worksheet that contains only a chart.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// let workbook = Workbook::new("test-workbook-add_chartsheet.xlsx");
+    /// let mut worksheet = workbook.add_worksheet(None)?;
+    /// let mut chartsheet1 = workbook.add_chartsheet(None)?;
+    /// let mut chartsheet2 = workbook.add_chartsheet(Some("Sales chart"))?;
+    /// # worksheet.write_number(0, 0, 1.0, None)?;
+    /// # let mut chart1 = workbook.add_chart(ChartType::Column);
+    /// # chart1.add_series(None, Some("=Sheet1!$A$1:$A$1"))?;
+    /// # let mut chart2 = workbook.add_chart(ChartType::Line);
+    /// # chart2.add_series(None, Some("=Sheet1!$A$1:$A$1"))?;
+    /// # chartsheet1.set_chart(&chart1)?;
+    /// # chartsheet2.set_chart(&chart2)?;
+    /// workbook.close()
+    /// # }
+    /// ```
+    ///
+    /// The chartsheet name must be a valid Excel worksheet name. If `sheet_name` is `None` the default Excel convention will be followed, i.e. Chart1, Chart2, etc.
+    pub fn add_chartsheet<'a>(
+        &'a self,
+        sheet_name: Option<&str>,
+    ) -> Result<Chartsheet<'a>, XlsxError> {
+        let name_vec = sheet_name
+            .map(|x| to_c_string(x).map(|y| y.into_bytes_with_nul()))
+            .transpose()?;
+        unsafe {
+            if let Some(sheet_name) = name_vec.as_ref() {
+                let result = libxlsxwriter_sys::workbook_validate_sheet_name(
+                    self.workbook,
+                    sheet_name.as_ptr() as *const c_char,
+                );
+                if result != libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                    return Err(XlsxError::new(result));
+                }
+            }
+
+            let chartsheet = libxlsxwriter_sys::workbook_add_chartsheet(
+                self.workbook,
+                name_vec
+                    .as_ref()
+                    .map(|x| x.as_ptr() as *const c_char)
+                    .unwrap_or(std::ptr::null()),
+            );
+
+            if let Some(name) = name_vec {
+                self.const_str.borrow_mut().push(name);
+            }
+
+            if chartsheet.is_null() {
+                return Err(XlsxError::new(error::UNKNOWN_ERROR_CODE));
+            }
+
+            Ok(Chartsheet {
+                _workbook: self,
+                chartsheet,
             })
         }
     }
@@ -152,10 +256,7 @@ impl Workbook {
         unsafe {
             let worksheet = libxlsxwriter_sys::workbook_get_worksheet_by_name(
                 self.workbook,
-                CString::new(sheet_name)
-                    .expect("Null Error")
-                    .as_c_str()
-                    .as_ptr(),
+                to_c_string(sheet_name).ok()?.as_ptr(),
             );
             if worksheet.is_null() {
                 None
@@ -163,6 +264,7 @@ impl Workbook {
                 Some(Worksheet {
                     _workbook: self,
                     worksheet,
+                    autofit_enabled: self.text_widths.borrow().contains_key(&worksheet),
                 })
             }
         }
@@ -182,7 +284,59 @@ impl Workbook {
         }
     }
 
-    pub fn add_chart(&self, chart_type: ChartType) -> Chart {
+    /// Add a new format and set its properties with a closure. This is the same as calling the setters on the result of `add_format()`,
+    /// but keeps the definition of each format in one expression:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-workbook_add_format_with-1.xlsx");
+    /// let header = workbook.add_format_with(|format| {
+    ///     format
+    ///         .set_bold()
+    ///         .set_font_color(FormatColor::White)
+    ///         .set_bg_color(FormatColor::Navy)
+    /// });
+    /// let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_string(0, 0, "Name", Some(&header))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn add_format_with<'a, F>(&'a self, build: F) -> Format<'a>
+    where
+        F: FnOnce(Format<'a>) -> Format<'a>,
+    {
+        build(self.add_format())
+    }
+
+    /// Return the workbook's default hyperlink format, the blue underlined format that [`Worksheet::write_url()`] uses when no format is given.
+    /// This is useful to give other cells the same look as the links, e.g. when the displayed text of a link is overwritten:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-workbook-url_format-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let url_format = workbook.url_format();
+    /// worksheet.write_url(0, 0, "https://libxlsxwriter.github.io", None)?;
+    /// worksheet.write_string(0, 0, "Read the documentation.", Some(&url_format))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// The format is shared, so calling setters on it changes every hyperlink written without an explicit format.
+    pub fn url_format<'a>(&'a self) -> Format<'a> {
+        unsafe {
+            let format = libxlsxwriter_sys::workbook_get_default_url_format(self.workbook);
+            if format.is_null() {
+                unreachable!();
+            }
+
+            Format {
+                _workbook: self,
+                format,
+            }
+        }
+    }
+
+    pub fn add_chart<'a>(&'a self, chart_type: ChartType) -> Chart<'a> {
         unsafe {
             let chart = libxlsxwriter_sys::workbook_add_chart(self.workbook, chart_type.value());
             if chart.is_null() {
@@ -192,10 +346,60 @@ impl Workbook {
             Chart {
                 _workbook: self,
                 chart,
+                width: crate::chart::DEFAULT_CHART_WIDTH,
+                height: crate::chart::DEFAULT_CHART_HEIGHT,
             }
         }
     }
 
+    /// Set the default author of the comments in all the worksheets of the workbook, including worksheets added later:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// let workbook = Workbook::new("test-workbook-set_comments_author-1.xlsx");
+    /// let mut worksheet1 = workbook.add_worksheet(None)?;
+    /// workbook.set_comments_author("Reviewer")?;
+    /// let mut worksheet2 = workbook.add_worksheet(None)?;
+    /// worksheet1.write_comment(0, 0, "Written by Reviewer")?;
+    /// worksheet2.write_comment(0, 0, "Also written by Reviewer")?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// This is the same as calling [`Worksheet::set_comments_author()`] on every worksheet. The most specific author wins: the `author`
+    /// of a comment's [`CommentOptions`](type.CommentOptions.html) overrides the worksheet's author, and the worksheet's author is
+    /// whichever of `Worksheet::set_comments_author()` and `Workbook::set_comments_author()` was called last.
+    pub fn set_comments_author(&self, author: &str) -> Result<(), XlsxError> {
+        let author = to_c_string(author)?;
+        for worksheet in self.worksheets.borrow().iter() {
+            unsafe {
+                libxlsxwriter_sys::worksheet_set_comments_author(*worksheet, author.as_ptr());
+            }
+        }
+        *self.comments_author.borrow_mut() = Some(author);
+        Ok(())
+    }
+
+    /// Choose how [`Worksheet::write()`] and the other functions that take a [`CellValue`] handle a `CellValue::Number` that is NaN,
+    /// Inf or -Inf. By default they return an error like [`Worksheet::write_number()`]. With `blank` set to `true` a blank cell is
+    /// written instead, so the data can be written without checking every value first:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// let workbook = Workbook::new("test-workbook-set_non_finite_as_blank-1.xlsx");
+    /// let mut worksheet = workbook.add_worksheet(None)?;
+    /// assert!(worksheet.write(0, 0, f64::NAN, None).is_err());
+    /// workbook.set_non_finite_as_blank(true);
+    /// worksheet.write(0, 0, f64::NAN, None)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// The setting applies to all the worksheets of the workbook. `write_number()` always returns an error for these values, and
+    /// [`Worksheet::write_number_lossy()`] writes them as text. Like [`Worksheet::write_blank()`], a blank cell without a format is
+    /// ignored by Excel, so the cell is left empty.
+    pub fn set_non_finite_as_blank(&self, blank: bool) {
+        self.non_finite_as_blank.set(blank);
+    }
+
     /// This function is used to defined a name that can be used to represent a value,
     /// a single cell or a range of cells in a workbook:
     /// These defined names can then be used in formulas:
@@ -213,11 +417,115 @@ impl Workbook {
         unsafe {
             let result = libxlsxwriter_sys::workbook_define_name(
                 self.workbook,
-                CString::new(name).expect("Null Error").as_c_str().as_ptr(),
-                CString::new(formula)
-                    .expect("Null Error")
-                    .as_c_str()
-                    .as_ptr(),
+                to_c_string(name)?.as_ptr(),
+                to_c_string(formula)?.as_ptr(),
+            );
+
+            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                Ok(())
+            } else {
+                Err(XlsxError::new(result))
+            }
+        }
+    }
+
+    /// This function adds a VBA project to the workbook, so that it can contain macros. The VBA project is a `vbaProject.bin` file
+    /// extracted from an existing macro enabled workbook, e.g. with the `vba_extract.py` utility of the Python XlsxWriter:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # std::fs::write("vbaProject.bin", [0xcc, 0x61, 0xff, 0xff, 0x00, 0x00]).unwrap();
+    /// let workbook = Workbook::new("test-workbook-add_vba_project-1.xlsm");
+    /// let mut worksheet = workbook.add_worksheet(None)?;
+    /// workbook.add_vba_project("vbaProject.bin")?;
+    /// worksheet.insert_button(
+    ///     1,
+    ///     1,
+    ///     &ButtonOptions {
+    ///         caption: Some("Press Me".to_string()),
+    ///         macro_name: Some("say_hello".to_string()),
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Excel refuses to open a workbook with macros unless the file name ends in `.xlsm`. The VBA project file is read when the
+    /// workbook is closed, so it must not be removed before that. Use [`Workbook::add_vba_project_buffer()`] for a VBA project
+    /// that is already in memory.
+    pub fn add_vba_project(&self, filename: &str) -> Result<(), XlsxError> {
+        unsafe {
+            let result = libxlsxwriter_sys::workbook_add_vba_project(
+                self.workbook,
+                to_c_string(filename)?.as_ptr(),
+            );
+
+            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                Ok(())
+            } else {
+                Err(XlsxError::new(result))
+            }
+        }
+    }
+
+    /// This function is the same as [`Workbook::add_vba_project()`] but takes the content of the `vbaProject.bin` file, e.g. from
+    /// `include_bytes!()`:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let vba_project = [0xcc, 0x61, 0xff, 0xff, 0x00, 0x00];
+    /// let workbook = Workbook::new("test-workbook-add_vba_project_buffer-1.xlsm");
+    /// let mut worksheet = workbook.add_worksheet(None)?;
+    /// workbook.add_vba_project_buffer(&vba_project)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// libxlsxwriter only reads VBA projects from a file, so the buffer is written to a new file with a unique name in the system temp
+    /// directory, which is removed once the workbook is closed or dropped, or when another VBA project replaces it.
+    pub fn add_vba_project_buffer(&self, buffer: &[u8]) -> Result<(), XlsxError> {
+        let path = create_temp_file("xlsxwriter-vbaProject", buffer)
+            .map_err(|_| XlsxError::new(libxlsxwriter_sys::lxw_error_LXW_ERROR_CREATING_TMPFILE))?;
+        let result = match path.to_str() {
+            Some(filename) => self.add_vba_project(filename),
+            None => Err(XlsxError::new(
+                libxlsxwriter_sys::lxw_error_LXW_ERROR_CREATING_TMPFILE,
+            )),
+        };
+        if result.is_err() {
+            let _ = std::fs::remove_file(&path);
+            return result;
+        }
+        // libxlsxwriter only reads the last VBA project added, so the previous file isn't needed anymore.
+        if let Some(previous) = self.vba_project_file.borrow_mut().replace(path) {
+            let _ = std::fs::remove_file(previous);
+        }
+        Ok(())
+    }
+
+    fn remove_vba_project_file(&self) {
+        if let Some(path) = self.vba_project_file.borrow_mut().take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Set the VBA code name of the workbook, which macros use to refer to it. Excel's default is `ThisWorkbook`:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// let workbook = Workbook::new("test-workbook-set_vba_name-1.xlsm");
+    /// let mut worksheet = workbook.add_worksheet(None)?;
+    /// workbook.set_vba_name("MyWorkbook")?;
+    /// worksheet.set_vba_name("MySheet1")?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// This is only needed when the VBA project added with [`Workbook::add_vba_project()`] uses custom code names. The name should be a valid
+    /// VBA identifier: letters, digits and underscores, starting with a letter.
+    pub fn set_vba_name(&self, name: &str) -> Result<(), XlsxError> {
+        unsafe {
+            let result = libxlsxwriter_sys::workbook_set_vba_name(
+                self.workbook,
+                to_c_string(name)?.as_ptr(),
             );
 
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -228,10 +536,16 @@ impl Workbook {
         }
     }
 
+    /// This function is used to create the XLSX file from the workbook data and to write it to disk.
+    ///
+    /// If a workbook is dropped without calling `close()` it is closed automatically, so the file is still written. However,
+    /// any error raised while closing a dropped workbook is silently ignored. Call `close()` explicitly to find out whether
+    /// the file was written successfully.
     pub fn close(mut self) -> Result<(), XlsxError> {
         unsafe {
             let result = libxlsxwriter_sys::workbook_close(self.workbook);
             self.workbook = std::ptr::null_mut();
+            self.remove_vba_project_file();
             match result {
                 libxlsxwriter_sys::lxw_error_LXW_NO_ERROR => Ok(()),
                 _ => Err(XlsxError::new(result)),
@@ -241,11 +555,51 @@ impl Workbook {
 }
 
 impl Drop for Workbook {
+    /// Closes the workbook if [`Workbook::close()`] has not been called and removes the file written by
+    /// [`Workbook::add_vba_project_buffer()`]. Errors are swallowed.
     fn drop(&mut self) {
         unsafe {
             if !self.workbook.is_null() {
                 libxlsxwriter_sys::workbook_close(self.workbook);
             }
         }
+        self.remove_vba_project_file();
+    }
+}
+
+/// Write `buffer` to a new file in the system temp directory and return its path. The file is created with `create_new`, so an
+/// existing file or symbolic link with the same name is never written to, and another name is tried instead.
+pub(crate) fn create_temp_file(prefix: &str, buffer: &[u8]) -> std::io::Result<PathBuf> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut attempts = 0;
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.subsec_nanos())
+            .unwrap_or(0);
+        let path = std::env::temp_dir().join(format!(
+            "{}-{}-{}-{}.bin",
+            prefix,
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+            nanos
+        ));
+        match options.open(&path) {
+            Ok(mut file) => {
+                if let Err(e) = file.write_all(buffer) {
+                    let _ = std::fs::remove_file(&path);
+                    return Err(e);
+                }
+                return Ok(path);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && attempts < 100 => {
+                attempts += 1;
+            }
+            Err(e) => return Err(e),
+        }
     }
 }
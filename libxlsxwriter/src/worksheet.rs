@@ -1,13 +1,32 @@
 use crate::conditional_formatting::ConditionalFormat;
 
-use super::{convert_bool, Chart, DataValidation, Format, FormatColor, Workbook, XlsxError};
+use super::{
+    convert_bool, option_to_c_string, to_c_string, with_c_str, Chart, DataValidation, FitMode,
+    Format, FormatColor, ImageDimensions, Workbook, XlsxError,
+};
+use std::convert::TryFrom;
 use std::ffi::CString;
 use std::os::raw::c_char;
 
-fn option_string_to_raw_pointer(value: Option<&str>) -> *mut std::os::raw::c_char {
-    value
-        .map(|x| CString::new(x).expect("CString::new failed").into_raw())
-        .unwrap_or(std::ptr::null_mut())
+/// Number of characters of the longest line of `text`, for `Worksheet::autofit()`.
+pub(crate) fn text_width(text: &str) -> usize {
+    text.lines().map(|x| x.chars().count()).max().unwrap_or(0)
+}
+
+/// Approximate number of characters Excel shows for `number` in the General format, without allocating a string.
+pub(crate) fn number_width(number: f64) -> usize {
+    let sign = if number < 0. { 1 } else { 0 };
+    if number.fract() == 0. && number.abs() < 1e11 {
+        let digits = if number == 0. {
+            1
+        } else {
+            number.abs().log10().floor() as usize + 1
+        };
+        sign + digits
+    } else {
+        // The General format shows at most 11 characters and switches to scientific notation for large numbers.
+        11
+    }
 }
 
 /// Structure to set the options of a table column.
@@ -18,13 +37,14 @@ pub struct TableColumn<'a> {
     /// Set the header name/caption for the column. If NULL the header defaults to Column 1, Column 2, etc.
     pub header: Option<String>,
 
-    /// Set the formula for the column.
+    /// Set the formula for the column. Table formulas usually use structured references such as `=[@Price]*[@Quantity]`,
+    /// where `@` refers to the value in the same row. See [`TableColumn::structured_formula()`].
     pub formula: Option<String>,
 
-    /// Set the string description for the column total.
+    /// Set the string description for the column total. This can't be combined with `total_function`.
     pub total_string: Option<String>,
 
-    /// Set the function for the column total.
+    /// Set the function for the column total. This can't be combined with `total_string`.
     pub total_function: TableTotalFunction,
 
     /// Set the format for the column header.
@@ -37,20 +57,85 @@ pub struct TableColumn<'a> {
     pub total_value: f64,
 }
 
-impl<'a> From<TableColumn<'a>> for libxlsxwriter_sys::lxw_table_column {
-    fn from(c: TableColumn<'a>) -> libxlsxwriter_sys::lxw_table_column {
-        libxlsxwriter_sys::lxw_table_column {
-            header: option_string_to_raw_pointer(c.header.as_deref()),
-            formula: option_string_to_raw_pointer(c.formula.as_deref()),
-            total_string: option_string_to_raw_pointer(c.total_string.as_deref()),
-            total_function: c.total_function.into(),
-            header_format: c
+impl<'a> TableColumn<'a> {
+    /// Create a calculated column from a formula with structured references. `[@Name]` refers to the value of the `Name` column in the
+    /// same row and `[Name]` to the whole column. The leading `=` is optional:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_table_structured_formula-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_number(1, 0, 2., None)?;
+    /// worksheet.write_number(1, 1, 10., None)?;
+    /// let columns = vec![
+    ///     TableColumn {
+    ///         header: Some("Quantity".to_string()),
+    ///         ..Default::default()
+    ///     },
+    ///     TableColumn {
+    ///         header: Some("Price".to_string()),
+    ///         ..Default::default()
+    ///     },
+    ///     TableColumn {
+    ///         header: Some("Total".to_string()),
+    ///         ..TableColumn::structured_formula("[@Quantity]*[@Price]")
+    ///     },
+    /// ];
+    /// worksheet.add_table(0, 0, 1, 2, Some(TableOptions {
+    ///     columns: Some(columns),
+    ///     ..Default::default()
+    /// }))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Column names in the references must match the headers of the table.
+    pub fn structured_formula(expr: &str) -> TableColumn<'a> {
+        let formula = if expr.starts_with('=') {
+            expr.to_string()
+        } else {
+            format!("={}", expr)
+        };
+        TableColumn {
+            formula: Some(formula),
+            ..Default::default()
+        }
+    }
+
+    /// Copy the column without its `header_format` and `format`. [`Format`] can't be cloned, so set the formats of the copy again
+    /// if it needs them. See [`TableOptions::clone_without_formats()`].
+    pub fn clone_without_formats(&self) -> TableColumn<'a> {
+        TableColumn {
+            header: self.header.clone(),
+            formula: self.formula.clone(),
+            total_string: self.total_string.clone(),
+            total_function: self.total_function,
+            header_format: None,
+            format: None,
+            total_value: self.total_value,
+        }
+    }
+
+    fn to_lxw_table_column(
+        &self,
+        strings: &mut Vec<CString>,
+    ) -> Result<libxlsxwriter_sys::lxw_table_column, XlsxError> {
+        Ok(libxlsxwriter_sys::lxw_table_column {
+            header: option_to_c_string(self.header.as_deref(), strings)?,
+            formula: option_to_c_string(self.formula.as_deref(), strings)?,
+            total_string: option_to_c_string(self.total_string.as_deref(), strings)?,
+            total_function: self.total_function.into(),
+            header_format: self
                 .header_format
+                .as_ref()
                 .map(|x| x.format)
                 .unwrap_or(std::ptr::null_mut()),
-            format: c.format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
-            total_value: c.total_value,
-        }
+            format: self
+                .format
+                .as_ref()
+                .map(|x| x.format)
+                .unwrap_or(std::ptr::null_mut()),
+            total_value: self.total_value,
+        })
     }
 }
 
@@ -69,6 +154,18 @@ impl Default for TableStyleType {
     }
 }
 
+impl TableStyleType {
+    /// Return the valid range of `style_type_number` for this style type. The `Default` style type ignores the number.
+    fn style_number_range(self) -> Option<std::ops::RangeInclusive<u8>> {
+        match self {
+            TableStyleType::Default => None,
+            TableStyleType::Light => Some(1..=21),
+            TableStyleType::Medium => Some(1..=28),
+            TableStyleType::Dark => Some(1..=11),
+        }
+    }
+}
+
 impl From<TableStyleType> for u8 {
     fn from(t: TableStyleType) -> u8 {
         (match t {
@@ -207,7 +304,9 @@ pub struct TableOptions<'a> {
     /// The `style_type` parameter can be used to set the style of the table, in conjunction with the style_type_number parameter.
     pub style_type: TableStyleType,
 
-    /// The `style_type_number` parameter is used with style_type to set the style of a worksheet table.     
+    /// The `style_type_number` parameter is used with style_type to set the style of a worksheet table.
+    ///
+    /// The valid numbers are 1 to 21 for `Light`, 1 to 28 for `Medium` and 1 to 11 for `Dark`. `Worksheet::add_table()` returns an error for a number outside of this range. The number is ignored for the `Default` style type.
     pub style_type_number: u8,
 
     /// The `total_row` parameter can be used to turn on the total row in the last row of a table. It is distinguished from the other rows by a different formatting and also with dropdown SUBTOTAL functions.
@@ -218,24 +317,108 @@ pub struct TableOptions<'a> {
 }
 
 impl<'a> TableOptions<'a> {
-    fn into_lxw_table_options(
-        self,
-    ) -> (
-        Option<Vec<libxlsxwriter_sys::lxw_table_column>>,
-        libxlsxwriter_sys::lxw_table_options,
-    ) {
-        let mut columns: Option<Vec<libxlsxwriter_sys::lxw_table_column>> = self
+    /// Create table options with the given style and every other option at its default. Combine it with the `highlight_*` methods
+    /// for the common cases instead of spelling out the whole struct:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_table_options_styled-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_string(0, 0, "Region", None)?;
+    /// worksheet.write_string(0, 1, "Sales", None)?;
+    /// worksheet.write_string(1, 0, "North", None)?;
+    /// worksheet.write_number(1, 1, 1250., None)?;
+    /// let options = TableOptions::styled(TableStyleType::Medium, 9).highlight_first_column();
+    /// worksheet.add_table(0, 0, 1, 1, Some(options))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn styled(style_type: TableStyleType, style_type_number: u8) -> TableOptions<'a> {
+        TableOptions {
+            style_type,
+            style_type_number,
+            ..Default::default()
+        }
+    }
+
+    /// Highlight the first column of the table. See [`TableOptions::first_column`].
+    pub fn highlight_first_column(mut self) -> TableOptions<'a> {
+        self.first_column = true;
+        self
+    }
+
+    /// Highlight the last column of the table. See [`TableOptions::last_column`].
+    pub fn highlight_last_column(mut self) -> TableOptions<'a> {
+        self.last_column = true;
+        self
+    }
+
+    /// Copy the options to use them as the base of another table. The column formats are not copied, see
+    /// [`TableColumn::clone_without_formats()`].
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_table_options_clone-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let base = TableOptions {
+    ///     total_row: true,
+    ///     ..TableOptions::styled(TableStyleType::Light, 9)
+    /// };
+    /// let mut first = base.clone_without_formats();
+    /// first.name = Some("Sales2022".to_string());
+    /// worksheet.add_table(0, 0, 3, 1, Some(first))?;
+    /// let mut second = base.clone_without_formats();
+    /// second.name = Some("Sales2023".to_string());
+    /// worksheet.add_table(5, 0, 8, 1, Some(second))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Table names must be unique in the workbook, so give each copy its own `name` when the base has one.
+    pub fn clone_without_formats(&self) -> TableOptions<'a> {
+        TableOptions {
+            name: self.name.clone(),
+            no_header_row: self.no_header_row,
+            no_autofilter: self.no_autofilter,
+            no_banded_rows: self.no_banded_rows,
+            banded_columns: self.banded_columns,
+            first_column: self.first_column,
+            last_column: self.last_column,
+            style_type: self.style_type,
+            style_type_number: self.style_type_number,
+            total_row: self.total_row,
+            columns: self
+                .columns
+                .as_ref()
+                .map(|x| x.iter().map(|y| y.clone_without_formats()).collect()),
+        }
+    }
+
+    fn to_lxw_table_options(&self) -> Result<LxwTableOptions, XlsxError> {
+        let mut strings = Vec::new();
+        let mut columns = self
             .columns
-            .map(|z| z.into_iter().map(|x| x.into()).collect());
+            .as_ref()
+            .map(|x| {
+                x.iter()
+                    .map(|y| y.to_lxw_table_column(&mut strings))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+        // libxlsxwriter expects a NULL terminated array of column pointers.
         let mut c_columns: Option<Vec<_>> = columns.as_mut().map(|x| {
             x.iter_mut()
                 .map(|y| y as *mut libxlsxwriter_sys::lxw_table_column)
+                .chain(std::iter::once(std::ptr::null_mut()))
                 .collect()
         });
-        (
-            columns,
-            libxlsxwriter_sys::lxw_table_options {
-                name: option_string_to_raw_pointer(self.name.as_deref()),
+        let columns_ptr = c_columns
+            .as_mut()
+            .map(|x| x.as_mut_ptr())
+            .unwrap_or(std::ptr::null_mut());
+        let name = option_to_c_string(self.name.as_deref(), &mut strings)?;
+        Ok(LxwTableOptions {
+            options: libxlsxwriter_sys::lxw_table_options {
+                name,
                 no_header_row: convert_bool(self.no_header_row),
                 no_autofilter: convert_bool(self.no_autofilter),
                 no_banded_rows: convert_bool(self.no_banded_rows),
@@ -245,16 +428,28 @@ impl<'a> TableOptions<'a> {
                 style_type: self.style_type.into(),
                 style_type_number: self.style_type_number,
                 total_row: convert_bool(self.total_row),
-                columns: c_columns
-                    .as_mut()
-                    .map(|x| x.as_mut_ptr())
-                    .unwrap_or(std::ptr::null_mut()),
+                columns: columns_ptr,
             },
-        )
+            _strings: strings,
+            _columns: columns,
+            _c_columns: c_columns,
+        })
     }
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+/// `lxw_table_options` together with the strings and column arrays it points to, which must stay alive until
+/// `worksheet_add_table` returns. libxlsxwriter copies them into the table.
+struct LxwTableOptions {
+    options: libxlsxwriter_sys::lxw_table_options,
+    _strings: Vec<CString>,
+    _columns: Option<Vec<libxlsxwriter_sys::lxw_table_column>>,
+    _c_columns: Option<Vec<*mut libxlsxwriter_sys::lxw_table_column>>,
+}
+
+/// A date and time. `DateTime` is `Eq`, `Ord` and `Hash` so that it can be used as a key, comparing `second` with
+/// [`f64::total_cmp()`]: `-0.0` is the same as `0.0` and a NaN is equal to itself, e.g. `f64::NAN` sorts after every number.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DateTime {
     pub year: i16,
     pub month: i8,
@@ -275,6 +470,245 @@ impl DateTime {
             second,
         }
     }
+
+    /// Create a `DateTime` after checking that it is a valid date and time. Unlike `new()`, which accepts any value,
+    /// an error is returned if:
+    /// * the year is outside of 1900 to 9999, the range of dates supported by Excel
+    /// * the month is outside of 1 to 12, or the day doesn't exist in the month
+    /// * the hour is outside of 0 to 23, or the minute outside of 0 to 59
+    /// * the second is negative or 61 or more, a leap second is allowed
+    ///
+    /// A year, month and day of zero is accepted as a time only value, see [`DateTime::from_time()`].
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// let datetime = DateTime::try_new(2020, 2, 29, 13, 45, 30.)?;
+    /// assert_eq!(datetime, DateTime::new(2020, 2, 29, 13, 45, 30.));
+    /// assert!(DateTime::try_new(2021, 2, 29, 13, 45, 30.).is_err());
+    /// assert!(DateTime::try_new(2021, 13, 1, 0, 0, 0.).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_new(
+        year: i16,
+        month: i8,
+        day: i8,
+        hour: i8,
+        min: i8,
+        second: f64,
+    ) -> Result<DateTime, XlsxError> {
+        let time_only = year == 0 && month == 0 && day == 0;
+        if !time_only && !(1900..=9999).contains(&year) {
+            return Err(XlsxError::new(crate::error::DATETIME_OUT_OF_RANGE));
+        }
+        let valid_date = time_only
+            || ((1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month));
+        if !valid_date
+            || !(0..=23).contains(&hour)
+            || !(0..=59).contains(&min)
+            || !(0. ..61.).contains(&second)
+        {
+            return Err(XlsxError::new(crate::error::INVALID_DATETIME));
+        }
+        Ok(DateTime::new(year, month, day, hour, min, second))
+    }
+
+    /// Create a date at midnight.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// assert_eq!(DateTime::from_date(2013, 2, 28), DateTime::new(2013, 2, 28, 0, 0, 0.));
+    /// ```
+    pub fn from_date(year: i16, month: i8, day: i8) -> DateTime {
+        DateTime::new(year, month, day, 0, 0, 0.)
+    }
+
+    /// Create a time without a date. libxlsxwriter treats a year, month and day of zero as a time only value, which is stored
+    /// as a fraction of a day.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// assert_eq!(DateTime::from_time(12, 30, 0.), DateTime::new(0, 0, 0, 12, 30, 0.));
+    /// ```
+    pub fn from_time(hour: i8, min: i8, second: f64) -> DateTime {
+        DateTime::new(0, 0, 0, hour, min, second)
+    }
+
+    /// Return the Excel serial number of the date, the number of days since the epoch with the time as a fraction of a day.
+    /// This is the number stored in the cell by `write_datetime()`.
+    ///
+    /// With `date_1904` false the 1900 epoch used by default is applied: 1900-01-01 is day 1, and the nonexistent 1900-02-29 is
+    /// counted as in Excel, so dates from 1900-03-01 are one day later. With `date_1904` true, 1904-01-01 is day 0.
+    /// A time only value, see [`DateTime::from_time()`], is a fraction of a day in both cases.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// let datetime = DateTime::new(2013, 2, 28, 12, 0, 0.);
+    /// assert_eq!(datetime.to_serial(false), 41333.5);
+    /// assert_eq!(datetime.to_serial(true), 39871.5);
+    /// ```
+    pub fn to_serial(&self, date_1904: bool) -> f64 {
+        let time =
+            (f64::from(self.hour) * 3600. + f64::from(self.min) * 60. + self.second) / 86400.;
+        if self.year == 0 && self.month == 0 && self.day == 0 {
+            return time;
+        }
+        let days = days_from_civil(self.year, self.month, self.day);
+        let serial = if date_1904 {
+            days - days_from_civil(1904, 1, 1)
+        } else {
+            let serial = days - days_from_civil(1899, 12, 31);
+            // Excel counts 1900-02-29, which doesn't exist, as day 60.
+            if serial > 59 {
+                serial + 1
+            } else {
+                serial
+            }
+        };
+        serial as f64 + time
+    }
+
+    /// Copy of the date with the fractional part of the seconds removed.
+    pub(crate) fn with_whole_seconds(&self) -> DateTime {
+        DateTime {
+            second: self.second.trunc(),
+            ..self.clone()
+        }
+    }
+}
+
+// `second` is an `f64`, so `PartialEq`, `Eq` and `Hash` can't be derived. The seconds are compared by their bits, like in `Hash`,
+// so that a NaN second is equal to itself.
+impl DateTime {
+    /// `second` with `-0.0` replaced by `0.0`, for the comparison traits.
+    fn normalized_second(&self) -> f64 {
+        self.second + 0.
+    }
+}
+
+impl PartialEq for DateTime {
+    fn eq(&self, other: &DateTime) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for DateTime {}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &DateTime) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DateTime {
+    fn cmp(&self, other: &DateTime) -> std::cmp::Ordering {
+        (self.year, self.month, self.day, self.hour, self.min)
+            .cmp(&(other.year, other.month, other.day, other.hour, other.min))
+            .then_with(|| {
+                self.normalized_second()
+                    .total_cmp(&other.normalized_second())
+            })
+    }
+}
+
+impl std::hash::Hash for DateTime {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.year.hash(state);
+        self.month.hash(state);
+        self.day.hash(state);
+        self.hour.hash(state);
+        self.min.hash(state);
+        // total_cmp() is Equal exactly when the bits are equal.
+        self.normalized_second().to_bits().hash(state);
+    }
+}
+
+/// Number of days from 1970-01-01 to a date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i16, month: i8, day: i8) -> i64 {
+    let (year, month, day) = (i64::from(year), i64::from(month), i64::from(day));
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Number of days of `month` in the Gregorian calendar.
+fn days_in_month(year: i16, month: i8) -> i8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDate> for DateTime {
+    type Error = XlsxError;
+
+    /// Convert a date to a `DateTime` at midnight. An error is returned if the year doesn't fit in an `i16`.
+    fn try_from(date: chrono::NaiveDate) -> Result<Self, Self::Error> {
+        use chrono::Datelike;
+        let year = i16::try_from(date.year())
+            .map_err(|_| XlsxError::new(crate::error::DATETIME_OUT_OF_RANGE))?;
+        Ok(DateTime::new(
+            year,
+            date.month() as i8,
+            date.day() as i8,
+            0,
+            0,
+            0.,
+        ))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDateTime> for DateTime {
+    type Error = XlsxError;
+
+    /// Convert a date and time to a `DateTime`. Nanoseconds are kept as the fractional part of `second`. An error is returned if the year doesn't fit in an `i16`.
+    fn try_from(datetime: chrono::NaiveDateTime) -> Result<Self, Self::Error> {
+        use chrono::Timelike;
+        let mut result = DateTime::try_from(datetime.date())?;
+        result.hour = datetime.hour() as i8;
+        result.min = datetime.minute() as i8;
+        result.second =
+            f64::from(datetime.second()) + f64::from(datetime.nanosecond()) / 1_000_000_000.;
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::Date> for DateTime {
+    type Error = XlsxError;
+
+    /// Convert a date to a `DateTime` at midnight. An error is returned if the year doesn't fit in an `i16`.
+    fn try_from(date: time::Date) -> Result<Self, Self::Error> {
+        let year = i16::try_from(date.year())
+            .map_err(|_| XlsxError::new(crate::error::DATETIME_OUT_OF_RANGE))?;
+        Ok(DateTime::new(
+            year,
+            u8::from(date.month()) as i8,
+            date.day() as i8,
+            0,
+            0,
+            0.,
+        ))
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::PrimitiveDateTime> for DateTime {
+    type Error = XlsxError;
+
+    /// Convert a date and time to a `DateTime`. Nanoseconds are kept as the fractional part of `second`. An error is returned if the year doesn't fit in an `i16`.
+    fn try_from(datetime: time::PrimitiveDateTime) -> Result<Self, Self::Error> {
+        let mut result = DateTime::try_from(datetime.date())?;
+        result.hour = datetime.hour() as i8;
+        result.min = datetime.minute() as i8;
+        result.second =
+            f64::from(datetime.second()) + f64::from(datetime.nanosecond()) / 1_000_000_000.;
+        Ok(result)
+    }
 }
 
 impl From<&DateTime> for libxlsxwriter_sys::lxw_datetime {
@@ -290,6 +724,67 @@ impl From<&DateTime> for libxlsxwriter_sys::lxw_datetime {
     }
 }
 
+/// A value that can be written to a worksheet cell.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum CellValue {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Formula(String),
+    DateTime(DateTime),
+    Blank,
+}
+
+impl From<f64> for CellValue {
+    fn from(value: f64) -> Self {
+        CellValue::Number(value)
+    }
+}
+
+macro_rules! cell_value_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for CellValue {
+                fn from(value: $t) -> Self {
+                    CellValue::Number(value.into())
+                }
+            }
+        )*
+    };
+}
+
+cell_value_from_number!(f32, i8, u8, i16, u16, i32, u32);
+
+impl From<bool> for CellValue {
+    fn from(value: bool) -> Self {
+        CellValue::Bool(value)
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(value: &str) -> Self {
+        CellValue::String(value.to_string())
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(value: String) -> Self {
+        CellValue::String(value)
+    }
+}
+
+impl From<DateTime> for CellValue {
+    fn from(value: DateTime) -> Self {
+        CellValue::DateTime(value)
+    }
+}
+
+impl From<&DateTime> for CellValue {
+    fn from(value: &DateTime) -> Self {
+        CellValue::DateTime(value.clone())
+    }
+}
+
 /// Options for modifying images inserted via [Worksheet.insert_image_opt()](struct.Worksheet.html#method.insert_image_opt).
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct ImageOptions {
@@ -301,6 +796,60 @@ pub struct ImageOptions {
     pub x_scale: f64,
     /// Y scale of the image as a decimal.
     pub y_scale: f64,
+    /// Hyperlink opened when the image is clicked. The same forms as [`Worksheet::write_url()`] are supported. Charts ignore it.
+    pub url: Option<String>,
+    /// Tooltip shown when the mouse is over an image with a `url`. Charts ignore it.
+    pub tip: Option<String>,
+}
+
+impl Default for ImageOptions {
+    fn default() -> ImageOptions {
+        ImageOptions {
+            x_offset: 0,
+            y_offset: 0,
+            x_scale: 1.,
+            y_scale: 1.,
+            url: None,
+            tip: None,
+        }
+    }
+}
+
+/// Convert `options` and pass them to `f`. The converted options are only valid during the call.
+fn with_image_options<T>(
+    options: &ImageOptions,
+    f: impl FnOnce(&mut libxlsxwriter_sys::lxw_image_options) -> T,
+) -> Result<T, XlsxError> {
+    if let Some(url) = options.url.as_deref() {
+        check_url(url)?;
+    }
+    // libxlsxwriter copies the strings, so they only have to outlive the call.
+    let url = options.url.as_deref().map(to_c_string).transpose()?;
+    let tip = options.tip.as_deref().map(to_c_string).transpose()?;
+    let as_ptr = |x: &Option<CString>| {
+        x.as_ref()
+            .map(|y| y.as_ptr() as *mut c_char)
+            .unwrap_or(std::ptr::null_mut())
+    };
+    let mut opt_struct: libxlsxwriter_sys::lxw_image_options = options.into();
+    opt_struct.url = as_ptr(&url);
+    opt_struct.tip = as_ptr(&tip);
+    Ok(f(&mut opt_struct))
+}
+
+/// Options for buttons inserted via [Worksheet.insert_button()](struct.Worksheet.html#method.insert_button).
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub struct ButtonOptions {
+    /// Text shown on the button. Defaults to `Button 1`, `Button 2` and so on.
+    pub caption: Option<String>,
+    /// Name of the VBA macro run when the button is clicked.
+    pub macro_name: Option<String>,
+    /// Alternative text of the button for accessibility.
+    pub description: Option<String>,
+    /// Width of the button in pixels. Zero means the default width of 64 pixels.
+    pub width: u16,
+    /// Height of the button in pixels. Zero means the default height of 20 pixels.
+    pub height: u16,
 }
 
 impl From<&ImageOptions> for libxlsxwriter_sys::lxw_image_options {
@@ -319,30 +868,162 @@ impl From<&ImageOptions> for libxlsxwriter_sys::lxw_image_options {
     }
 }
 
+/// Paper size used when printing a worksheet. Each variant maps to the paper size code Excel uses, see [`PaperType::code()`].
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub enum PaperType {
+    /// Printer default
     PrinterDefault,
+    /// Letter, 8 1/2 x 11 in
     Letter,
+    /// Letter Small, 8 1/2 x 11 in
+    LetterSmall,
+    /// Tabloid, 11 x 17 in
     Tabloid,
+    /// Ledger, 17 x 11 in
     Ledger,
+    /// Legal, 8 1/2 x 14 in
     Legal,
+    /// Statement, 5 1/2 x 8 1/2 in
     Statement,
+    /// Executive, 7 1/4 x 10 1/2 in
     Executive,
+    /// A3, 297 x 420 mm
     A3,
+    /// A4, 210 x 297 mm
     A4,
+    /// A4 Small, 210 x 297 mm
+    A4Small,
+    /// A5, 148 x 210 mm
     A5,
+    /// B4, 250 x 354 mm
     B4,
+    /// B5, 182 x 257 mm
     B5,
+    /// Folio, 8 1/2 x 13 in
     Folio,
+    /// Quarto, 215 x 275 mm
     Quarto,
+    /// 10 x 14 in
+    Paper10x14,
+    /// 11 x 17 in
+    Paper11x17,
+    /// Note, 8 1/2 x 11 in
+    Note,
+    /// Envelope #9, 3 7/8 x 8 7/8 in
+    Envelope9,
+    /// Envelope #10, 4 1/8 x 9 1/2 in
+    Envelope10,
+    /// Envelope #11, 4 1/2 x 10 3/8 in
+    Envelope11,
+    /// Envelope #12, 4 3/4 x 11 in
+    Envelope12,
+    /// Envelope #14, 5 x 11 1/2 in
+    Envelope14,
+    /// C size sheet
+    CSheet,
+    /// D size sheet
+    DSheet,
+    /// E size sheet
+    ESheet,
+    /// Envelope DL, 110 x 220 mm
+    EnvelopeDL,
+    /// Envelope C3, 324 x 458 mm
+    EnvelopeC3,
+    /// Envelope C4, 229 x 324 mm
+    EnvelopeC4,
+    /// Envelope C5, 162 x 229 mm
+    EnvelopeC5,
+    /// Envelope C6, 114 x 162 mm
+    EnvelopeC6,
+    /// Envelope C65, 114 x 229 mm
+    EnvelopeC65,
+    /// Envelope B4, 250 x 353 mm
+    EnvelopeB4,
+    /// Envelope B5, 176 x 250 mm
+    EnvelopeB5,
+    /// Envelope B6, 176 x 125 mm
+    EnvelopeB6,
+    /// Italy envelope, 110 x 230 mm
+    EnvelopeItaly,
+    /// Monarch envelope, 3 7/8 x 7 1/2 in
+    EnvelopeMonarch,
+    /// 6 3/4 envelope, 3 5/8 x 6 1/2 in
+    Envelope6_3_4,
+    /// US standard fanfold, 14 7/8 x 11 in
+    UsStandardFanfold,
+    /// German standard fanfold, 8 1/2 x 12 in
+    GermanStandardFanfold,
+    /// German legal fanfold, 8 1/2 x 13 in
+    GermanLegalFanfold,
+    /// A2, 420 x 594 mm
+    A2,
+    /// Any other paper size code understood by Excel.
     Other(u8),
 }
 
 impl PaperType {
-    fn value(self) -> u8 {
-        let value = match self {
+    /// Convert an Excel paper size code to a `PaperType`. Codes without a named variant are returned as `PaperType::Other`.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// assert_eq!(PaperType::from_code(9), PaperType::A4);
+    /// assert_eq!(PaperType::from_code(PaperType::A4.code()), PaperType::A4);
+    /// assert_eq!(PaperType::from_code(100), PaperType::Other(100));
+    /// ```
+    pub fn from_code(code: u8) -> PaperType {
+        match code {
+            0 => PaperType::PrinterDefault,
+            1 => PaperType::Letter,
+            2 => PaperType::LetterSmall,
+            3 => PaperType::Tabloid,
+            4 => PaperType::Ledger,
+            5 => PaperType::Legal,
+            6 => PaperType::Statement,
+            7 => PaperType::Executive,
+            8 => PaperType::A3,
+            9 => PaperType::A4,
+            10 => PaperType::A4Small,
+            11 => PaperType::A5,
+            12 => PaperType::B4,
+            13 => PaperType::B5,
+            14 => PaperType::Folio,
+            15 => PaperType::Quarto,
+            16 => PaperType::Paper10x14,
+            17 => PaperType::Paper11x17,
+            18 => PaperType::Note,
+            19 => PaperType::Envelope9,
+            20 => PaperType::Envelope10,
+            21 => PaperType::Envelope11,
+            22 => PaperType::Envelope12,
+            23 => PaperType::Envelope14,
+            24 => PaperType::CSheet,
+            25 => PaperType::DSheet,
+            26 => PaperType::ESheet,
+            27 => PaperType::EnvelopeDL,
+            28 => PaperType::EnvelopeC3,
+            29 => PaperType::EnvelopeC4,
+            30 => PaperType::EnvelopeC5,
+            31 => PaperType::EnvelopeC6,
+            32 => PaperType::EnvelopeC65,
+            33 => PaperType::EnvelopeB4,
+            34 => PaperType::EnvelopeB5,
+            35 => PaperType::EnvelopeB6,
+            36 => PaperType::EnvelopeItaly,
+            37 => PaperType::EnvelopeMonarch,
+            38 => PaperType::Envelope6_3_4,
+            39 => PaperType::UsStandardFanfold,
+            40 => PaperType::GermanStandardFanfold,
+            41 => PaperType::GermanLegalFanfold,
+            66 => PaperType::A2,
+            x => PaperType::Other(x),
+        }
+    }
+
+    /// The Excel paper size code of this paper type.
+    pub fn code(self) -> u8 {
+        match self {
             PaperType::PrinterDefault => 0,
             PaperType::Letter => 1,
+            PaperType::LetterSmall => 2,
             PaperType::Tabloid => 3,
             PaperType::Ledger => 4,
             PaperType::Legal => 5,
@@ -350,17 +1031,51 @@ impl PaperType {
             PaperType::Executive => 7,
             PaperType::A3 => 8,
             PaperType::A4 => 9,
+            PaperType::A4Small => 10,
             PaperType::A5 => 11,
             PaperType::B4 => 12,
             PaperType::B5 => 13,
             PaperType::Folio => 14,
             PaperType::Quarto => 15,
-            PaperType::Other(x) => x.into(),
-        };
-        value as u8
+            PaperType::Paper10x14 => 16,
+            PaperType::Paper11x17 => 17,
+            PaperType::Note => 18,
+            PaperType::Envelope9 => 19,
+            PaperType::Envelope10 => 20,
+            PaperType::Envelope11 => 21,
+            PaperType::Envelope12 => 22,
+            PaperType::Envelope14 => 23,
+            PaperType::CSheet => 24,
+            PaperType::DSheet => 25,
+            PaperType::ESheet => 26,
+            PaperType::EnvelopeDL => 27,
+            PaperType::EnvelopeC3 => 28,
+            PaperType::EnvelopeC4 => 29,
+            PaperType::EnvelopeC5 => 30,
+            PaperType::EnvelopeC6 => 31,
+            PaperType::EnvelopeC65 => 32,
+            PaperType::EnvelopeB4 => 33,
+            PaperType::EnvelopeB5 => 34,
+            PaperType::EnvelopeB6 => 35,
+            PaperType::EnvelopeItaly => 36,
+            PaperType::EnvelopeMonarch => 37,
+            PaperType::Envelope6_3_4 => 38,
+            PaperType::UsStandardFanfold => 39,
+            PaperType::GermanStandardFanfold => 40,
+            PaperType::GermanLegalFanfold => 41,
+            PaperType::A2 => 66,
+            PaperType::Other(x) => x,
+        }
     }
 }
 
+/// Page orientation used when printing a worksheet.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct HeaderFooterOptions {
     pub margin: f64,
@@ -377,7 +1092,26 @@ impl From<&HeaderFooterOptions> for libxlsxwriter_sys::lxw_header_footer_options
     }
 }
 
+/// How the panes set by [`Worksheet::freeze_panes_opt()`] behave.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FreezePaneType {
+    /// Frozen panes, the same as [`Worksheet::freeze_panes()`].
+    Frozen,
+    /// Frozen panes that turn into split panes when they are unfrozen in Excel.
+    FrozenSplit,
+}
+
+impl FreezePaneType {
+    fn value(self) -> u8 {
+        match self {
+            FreezePaneType::Frozen => 0,
+            FreezePaneType::FrozenSplit => 1,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GridLines {
     HideAllGridLines,
     ShowScreenGridLines,
@@ -401,6 +1135,23 @@ impl GridLines {
     }
 }
 
+/// Print settings applied together by [`Worksheet::set_print_options()`].
+///
+/// A `false` or `None` field leaves the corresponding setting untouched, libxlsxwriter has no way to turn a print toggle off again once it is set.
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PrintOptions {
+    /// Center the data horizontally between the margins. See [`Worksheet::center_horizontally()`].
+    pub center_horizontally: bool,
+    /// Center the data vertically between the margins. See [`Worksheet::center_vertically()`].
+    pub center_vertically: bool,
+    /// Print the row numbers and column letters. See [`Worksheet::print_row_col_headers()`].
+    pub print_row_col_headers: bool,
+    /// Screen and print gridlines. See [`Worksheet::gridlines()`].
+    pub gridlines: Option<GridLines>,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
 pub struct Protection {
     pub no_select_locked_cells: bool,
@@ -476,52 +1227,416 @@ impl From<&Protection> for libxlsxwriter_sys::lxw_protection {
     }
 }
 
-/// Integer data type to represent a column value. Equivalent to `u16`.
-///
-/// The maximum column in Excel is 16,384.
-pub type WorksheetCol = libxlsxwriter_sys::lxw_col_t;
+/// The criteria used in a [`FilterRule`].
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum FilterCriteria {
+    EqualTo,
+    NotEqualTo,
+    GreaterThan,
+    LessThan,
+    GreaterThanOrEqualTo,
+    LessThanOrEqualTo,
+    /// Show only blank cells. The rule value is ignored.
+    Blanks,
+    /// Show only non-blank cells. The rule value is ignored.
+    NonBlanks,
+}
 
-/// Integer data type to represent a row value. Equivalent to `u32`.
-///
-/// The maximum row in Excel is 1,048,576.
-pub type WorksheetRow = libxlsxwriter_sys::lxw_row_t;
+impl FilterCriteria {
+    fn value(self) -> u8 {
+        let value = match self {
+            FilterCriteria::EqualTo => {
+                libxlsxwriter_sys::lxw_filter_criteria_LXW_FILTER_CRITERIA_EQUAL_TO
+            }
+            FilterCriteria::NotEqualTo => {
+                libxlsxwriter_sys::lxw_filter_criteria_LXW_FILTER_CRITERIA_NOT_EQUAL_TO
+            }
+            FilterCriteria::GreaterThan => {
+                libxlsxwriter_sys::lxw_filter_criteria_LXW_FILTER_CRITERIA_GREATER_THAN
+            }
+            FilterCriteria::LessThan => {
+                libxlsxwriter_sys::lxw_filter_criteria_LXW_FILTER_CRITERIA_LESS_THAN
+            }
+            FilterCriteria::GreaterThanOrEqualTo => {
+                libxlsxwriter_sys::lxw_filter_criteria_LXW_FILTER_CRITERIA_GREATER_THAN_OR_EQUAL_TO
+            }
+            FilterCriteria::LessThanOrEqualTo => {
+                libxlsxwriter_sys::lxw_filter_criteria_LXW_FILTER_CRITERIA_LESS_THAN_OR_EQUAL_TO
+            }
+            FilterCriteria::Blanks => {
+                libxlsxwriter_sys::lxw_filter_criteria_LXW_FILTER_CRITERIA_BLANKS
+            }
+            FilterCriteria::NonBlanks => {
+                libxlsxwriter_sys::lxw_filter_criteria_LXW_FILTER_CRITERIA_NON_BLANKS
+            }
+        };
+        value as u8
+    }
+}
 
-pub type CommentOptions = libxlsxwriter_sys::lxw_comment_options;
-pub type RowColOptions = libxlsxwriter_sys::lxw_row_col_options;
+/// The value compared against in a [`FilterRule`].
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum FilterValue {
+    Number(f64),
+    String(String),
+}
 
-pub const LXW_DEF_ROW_HEIGHT: f64 = 8.43;
-pub const LXW_DEF_ROW_HEIGHT_PIXELS: u32 = 20;
-pub const LXW_DEF_COL_WIDTH: f64 = 15.0;
-pub const LXW_DEF_COL_WIDTH_PIXELS: u32 = 64;
+impl From<f64> for FilterValue {
+    fn from(value: f64) -> FilterValue {
+        FilterValue::Number(value)
+    }
+}
 
-/// The Worksheet object represents an Excel worksheet. It handles operations such as writing data to cells or formatting worksheet layout.
-///
-/// A Worksheet object isn't created directly. Instead a worksheet is created by calling the `workbook.add_worksheet()` function from a [Workbook](struct.Workbook.html) object:
-/// ```rust
-/// use xlsxwriter::*;
-/// # fn main() -> Result<(), XlsxError> {
-/// let workbook = Workbook::new("test-worksheet.xlsx");
-/// let mut worksheet = workbook.add_worksheet(None)?;
-/// worksheet.write_string(0, 0, "Hello, excel", None)?;
-/// workbook.close()
-/// # }
-/// ```
-/// Please read [original libxlsxwriter document](https://libxlsxwriter.github.io/worksheet_8h.html) for description missing functions.
-/// Most of this document is based on libxlsxwriter document.
-pub struct Worksheet<'a> {
-    pub(crate) _workbook: &'a Workbook,
-    pub(crate) worksheet: *mut libxlsxwriter_sys::lxw_worksheet,
+impl From<&str> for FilterValue {
+    fn from(value: &str) -> FilterValue {
+        FilterValue::String(value.to_string())
+    }
 }
 
-impl<'a> Worksheet<'a> {
-    /// This function writes the comment of a cell
-    /// ```rust
-    /// # use xlsxwriter::*;
-    /// # fn main() -> Result<(), XlsxError> {
-    /// # let workbook = Workbook::new("test-worksheet_write_comment-1.xlsx");
-    /// # let mut worksheet = workbook.add_worksheet(None)?;
-    /// worksheet.write_comment(0, 0, "This is some comment text")?;
-    /// worksheet.write_comment(1, 0, "This cell also has a comment")?;
+/// Operator used to combine the two conditions of a [`FilterRule`].
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum FilterOperator {
+    And,
+    Or,
+}
+
+impl FilterOperator {
+    fn value(self) -> u8 {
+        let value = match self {
+            FilterOperator::And => libxlsxwriter_sys::lxw_filter_operator_LXW_FILTER_AND,
+            FilterOperator::Or => libxlsxwriter_sys::lxw_filter_operator_LXW_FILTER_OR,
+        };
+        value as u8
+    }
+}
+
+/// A filter rule used with [`Worksheet::filter_column()`]. A rule has one condition and can optionally be combined with a second one using [`FilterRule::and()`] or [`FilterRule::or()`].
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct FilterRule {
+    pub criteria: FilterCriteria,
+    pub value: FilterValue,
+    pub second: Option<(FilterOperator, FilterCriteria, FilterValue)>,
+}
+
+impl FilterRule {
+    pub fn new<V: Into<FilterValue>>(criteria: FilterCriteria, value: V) -> FilterRule {
+        FilterRule {
+            criteria,
+            value: value.into(),
+            second: None,
+        }
+    }
+
+    /// Combine this rule with a second condition. Both conditions must match.
+    pub fn and<V: Into<FilterValue>>(mut self, criteria: FilterCriteria, value: V) -> FilterRule {
+        self.second = Some((FilterOperator::And, criteria, value.into()));
+        self
+    }
+
+    /// Combine this rule with a second condition. Either condition must match.
+    pub fn or<V: Into<FilterValue>>(mut self, criteria: FilterCriteria, value: V) -> FilterRule {
+        self.second = Some((FilterOperator::Or, criteria, value.into()));
+        self
+    }
+}
+
+fn check_formula(formula: &str) -> Result<(), XlsxError> {
+    if formula.trim().is_empty() {
+        Err(XlsxError::new(crate::error::EMPTY_FORMULA))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_range(
+    first_row: WorksheetRow,
+    first_col: WorksheetCol,
+    last_row: WorksheetRow,
+    last_col: WorksheetCol,
+) -> Result<(), XlsxError> {
+    if first_row > last_row || first_col > last_col {
+        Err(XlsxError::new(crate::error::REVERSED_RANGE))
+    } else {
+        Ok(())
+    }
+}
+
+/// Column `offset` cells after `first_col`. It saturates, so that libxlsxwriter reports an out of range column instead of wrapping around.
+fn offset_col(first_col: WorksheetCol, offset: usize) -> WorksheetCol {
+    WorksheetCol::try_from(offset)
+        .ok()
+        .and_then(|x| first_col.checked_add(x))
+        .unwrap_or(WorksheetCol::MAX)
+}
+
+/// Row `offset` cells after `first_row`. See `offset_col()`.
+fn offset_row(first_row: WorksheetRow, offset: usize) -> WorksheetRow {
+    WorksheetRow::try_from(offset)
+        .ok()
+        .and_then(|x| first_row.checked_add(x))
+        .unwrap_or(WorksheetRow::MAX)
+}
+
+fn check_scale(scale: u16) -> Result<(), XlsxError> {
+    if (10..=400).contains(&scale) {
+        Ok(())
+    } else {
+        Err(XlsxError::new(crate::error::SCALE_OUT_OF_RANGE))
+    }
+}
+
+/// Check the form of a URL passed to `write_url()`, so that malformed links fail with a descriptive error instead of a generic
+/// libxlsxwriter error or a broken link.
+pub(crate) fn check_url(url: &str) -> Result<(), XlsxError> {
+    if let Some(location) = url.strip_prefix("internal:") {
+        // Sheet names may contain `!` when quoted, so split at the last one.
+        match location.rfind('!') {
+            _ if location.is_empty() => Err(XlsxError::new(crate::error::URL_MISSING_TARGET)),
+            Some(i) if i > 0 && i + 1 < location.len() => Ok(()),
+            Some(i) if i > 0 => Err(XlsxError::new(crate::error::URL_MISSING_TARGET)),
+            _ => Err(XlsxError::new(crate::error::URL_MISSING_SHEET)),
+        }
+    } else if let Some(path) = url.strip_prefix("external:") {
+        let (file, location) = match path.find('#') {
+            Some(i) => (&path[..i], Some(&path[i + 1..])),
+            None => (path, None),
+        };
+        if file.is_empty() || location.map(str::is_empty).unwrap_or(false) {
+            Err(XlsxError::new(crate::error::URL_MISSING_TARGET))
+        } else {
+            Ok(())
+        }
+    } else if url.starts_with("mailto:") {
+        if url.len() > "mailto:".len() {
+            Ok(())
+        } else {
+            Err(XlsxError::new(crate::error::URL_MISSING_TARGET))
+        }
+    } else {
+        match url.find("://") {
+            Some(i)
+                if i > 0
+                    && url[..i]
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '.' || c == '-') =>
+            {
+                if url.len() > i + 3 {
+                    Ok(())
+                } else {
+                    Err(XlsxError::new(crate::error::URL_MISSING_TARGET))
+                }
+            }
+            _ => Err(XlsxError::new(crate::error::URL_UNKNOWN_SCHEME)),
+        }
+    }
+}
+
+pub(crate) fn external_link_url(target: &str) -> String {
+    let (path, anchor) = match target.find('#') {
+        Some(i) => target.split_at(i),
+        None => (target, ""),
+    };
+    let path = if let Some(rest) = path.strip_prefix("file://") {
+        let rest = percent_decode(rest);
+        match rest.strip_prefix('/') {
+            // file:///C:/dir/file.xlsx
+            Some(local) if local.as_bytes().get(1) == Some(&b':') => local.to_string(),
+            // file:///dir/file.xlsx
+            Some(_) => rest,
+            // file://server/share/file.xlsx
+            None => format!("//{}", rest),
+        }
+    } else {
+        path.to_string()
+    };
+    // An absolute POSIX path is kept as it is, every other path is converted to Windows separators.
+    let path = if path.starts_with('/') && !path.starts_with("//") {
+        path
+    } else {
+        path.replace('/', "\\")
+    };
+    format!("external:{}{}", path, anchor)
+}
+
+/// Decode the `%XX` escapes of a URL. The URL is returned unchanged if an escape is invalid or the result isn't UTF-8.
+fn percent_decode(url: &str) -> String {
+    let mut bytes = Vec::with_capacity(url.len());
+    let mut rest = url.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2).and_then(|hex| std::str::from_utf8(hex).ok());
+            match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                Some(decoded) => bytes.push(decoded),
+                None => return url.to_string(),
+            }
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).unwrap_or_else(|_| url.to_string())
+}
+
+/// Offsets and scale to center an image of `image_size` in a cell of `cell_size`, both in pixels. The image is only scaled down.
+pub(crate) fn centered_image_options(
+    image_size: (u32, u32),
+    cell_size: (u32, u32),
+) -> ImageOptions {
+    let (image_width, image_height) = (f64::from(image_size.0), f64::from(image_size.1));
+    let (cell_width, cell_height) = (f64::from(cell_size.0), f64::from(cell_size.1));
+    let scale = if image_width == 0. || image_height == 0. {
+        1.
+    } else {
+        (cell_width / image_width)
+            .min(cell_height / image_height)
+            .min(1.)
+    };
+    ImageOptions {
+        x_offset: ((cell_width - image_width * scale) / 2.) as i32,
+        y_offset: ((cell_height - image_height * scale) / 2.) as i32,
+        x_scale: scale,
+        y_scale: scale,
+        ..Default::default()
+    }
+}
+
+/// Width in pixels of a column of `width` character units, the same as libxlsxwriter's `_worksheet_size_col()` which positions
+/// images and charts. Columns narrower than one character are scaled linearly, wider ones get the 5 pixel padding.
+pub(crate) fn column_width_pixels(width: f64) -> u32 {
+    // Width of a digit of the default 11 point Calibri font, and the cell padding, in pixels.
+    const MAX_DIGIT_WIDTH: f64 = 7.;
+    const PADDING: u32 = 5;
+    if width <= 0. {
+        0
+    } else if width < 1. {
+        (width * (MAX_DIGIT_WIDTH + f64::from(PADDING)) + 0.5) as u32
+    } else {
+        (width * MAX_DIGIT_WIDTH + 0.5) as u32 + PADDING
+    }
+}
+
+/// Height in pixels of a row of `height` points, the same as libxlsxwriter's `_worksheet_size_row()`.
+pub(crate) fn row_height_pixels(height: f64) -> u32 {
+    (4. / 3. * height) as u32
+}
+
+/// Natural size of the image file in pixels, for the functions that scale an image before inserting it. Only the header is read.
+fn image_file_size(filename: &str) -> Result<(u32, u32), XlsxError> {
+    crate::image::image_file_dimensions(filename)
+        .map_err(|_| XlsxError::new(crate::error::IMAGE_FILE_NOT_READABLE))?
+        .ok_or_else(|| XlsxError::new(libxlsxwriter_sys::lxw_error_LXW_ERROR_IMAGE_DIMENSIONS))
+}
+
+/// Offsets and scales to fit an image of `image_size` to a range of `range_size`, both in pixels.
+pub(crate) fn fit_image_options(
+    image_size: (u32, u32),
+    range_size: (u32, u32),
+    mode: FitMode,
+) -> ImageOptions {
+    let (image_width, image_height) = (f64::from(image_size.0), f64::from(image_size.1));
+    let (range_width, range_height) = (f64::from(range_size.0), f64::from(range_size.1));
+    if image_width == 0. || image_height == 0. {
+        return ImageOptions::default();
+    }
+    let (x_scale, y_scale) = (range_width / image_width, range_height / image_height);
+    match mode {
+        FitMode::Stretch => ImageOptions {
+            x_scale,
+            y_scale,
+            ..Default::default()
+        },
+        FitMode::Contain => {
+            let scale = x_scale.min(y_scale);
+            ImageOptions {
+                x_offset: ((range_width - image_width * scale) / 2.) as i32,
+                y_offset: ((range_height - image_height * scale) / 2.) as i32,
+                x_scale: scale,
+                y_scale: scale,
+                ..Default::default()
+            }
+        }
+        FitMode::Cover => {
+            let scale = x_scale.max(y_scale);
+            ImageOptions {
+                x_scale: scale,
+                y_scale: scale,
+                ..Default::default()
+            }
+        }
+    }
+}
+
+fn filter_rule_to_c_struct(
+    criteria: FilterCriteria,
+    value: &FilterValue,
+    value_string: &mut Option<CString>,
+) -> Result<libxlsxwriter_sys::lxw_filter_rule, XlsxError> {
+    let (value, string_ptr) = match value {
+        FilterValue::Number(x) => (*x, std::ptr::null_mut()),
+        FilterValue::String(x) => {
+            let c_string = value_string.insert(to_c_string(x)?);
+            (0., c_string.as_ptr() as *mut c_char)
+        }
+    };
+    Ok(libxlsxwriter_sys::lxw_filter_rule {
+        criteria: criteria.value(),
+        value_string: string_ptr,
+        value,
+    })
+}
+
+/// Integer data type to represent a column value. Equivalent to `u16`.
+///
+/// The maximum column in Excel is 16,384.
+pub type WorksheetCol = libxlsxwriter_sys::lxw_col_t;
+
+/// Integer data type to represent a row value. Equivalent to `u32`.
+///
+/// The maximum row in Excel is 1,048,576.
+pub type WorksheetRow = libxlsxwriter_sys::lxw_row_t;
+
+/// Options for [`Worksheet::write_comment_opt()`]. This is the raw libxlsxwriter struct, so `author` and `font_name` are C string pointers or null.
+///
+/// libxlsxwriter copies these strings while `write_comment_opt()` runs, so they only need to stay valid until it returns.
+pub type CommentOptions = libxlsxwriter_sys::lxw_comment_options;
+pub type RowColOptions = libxlsxwriter_sys::lxw_row_col_options;
+
+pub const LXW_DEF_ROW_HEIGHT: f64 = 8.43;
+pub const LXW_DEF_ROW_HEIGHT_PIXELS: u32 = 20;
+pub const LXW_DEF_COL_WIDTH: f64 = 15.0;
+pub const LXW_DEF_COL_WIDTH_PIXELS: u32 = 64;
+
+/// The Worksheet object represents an Excel worksheet. It handles operations such as writing data to cells or formatting worksheet layout.
+///
+/// A Worksheet object isn't created directly. Instead a worksheet is created by calling the `workbook.add_worksheet()` function from a [Workbook](struct.Workbook.html) object:
+/// ```rust
+/// use xlsxwriter::*;
+/// # fn main() -> Result<(), XlsxError> {
+/// let workbook = Workbook::new("test-worksheet.xlsx");
+/// let mut worksheet = workbook.add_worksheet(None)?;
+/// worksheet.write_string(0, 0, "Hello, excel", None)?;
+/// workbook.close()
+/// # }
+/// ```
+/// Please read [original libxlsxwriter document](https://libxlsxwriter.github.io/worksheet_8h.html) for description missing functions.
+/// Most of this document is based on libxlsxwriter document.
+pub struct Worksheet<'a> {
+    pub(crate) _workbook: &'a Workbook,
+    pub(crate) worksheet: *mut libxlsxwriter_sys::lxw_worksheet,
+    /// Whether `enable_autofit()` was called for this worksheet, so that writing to other worksheets doesn't borrow `text_widths`.
+    pub(crate) autofit_enabled: bool,
+}
+
+impl<'a> Worksheet<'a> {
+    /// This function writes the comment of a cell
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_comment-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_comment(0, 0, "This is some comment text")?;
+    /// worksheet.write_comment(1, 0, "This cell also has a comment")?;
     /// # workbook.close()
     /// # }
     /// ```
@@ -536,7 +1651,7 @@ impl<'a> Worksheet<'a> {
                 self.worksheet,
                 row,
                 col,
-                CString::new(text).unwrap().as_c_str().as_ptr(),
+                to_c_string(text)?.as_ptr(),
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
                 Ok(())
@@ -546,6 +1661,35 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// This function is the same as `write_comment()` but takes a `CommentOptions` struct to set the author, size, color, font and position of the comment:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # use std::ffi::CString;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_comment_opt-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let author = CString::new("Ada").unwrap();
+    /// let mut options = CommentOptions {
+    ///     visible: 0,
+    ///     author: author.as_ptr() as *mut _,
+    ///     width: 0,
+    ///     height: 0,
+    ///     x_scale: 0.,
+    ///     y_scale: 0.,
+    ///     color: 0,
+    ///     font_name: std::ptr::null_mut(),
+    ///     font_size: 0.,
+    ///     font_family: 0,
+    ///     start_row: 0,
+    ///     start_col: 0,
+    ///     x_offset: 0,
+    ///     y_offset: 0,
+    /// };
+    /// worksheet.write_comment_opt(0, 0, "Reviewed", &mut options)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Zero values mean that the libxlsxwriter default is used. The `author` and `font_name` pointers must be null or point to NUL terminated strings that are alive during the call, e.g. owned by a `CString` kept in a variable as above. Don't use `CString::new(..).unwrap().as_ptr()` in the struct literal because the `CString` is dropped at the end of that statement.
     pub fn write_comment_opt(
         &mut self,
         row: WorksheetRow,
@@ -558,7 +1702,7 @@ impl<'a> Worksheet<'a> {
                 self.worksheet,
                 row,
                 col,
-                CString::new(text).unwrap().as_c_str().as_ptr(),
+                to_c_string(text)?.as_ptr(),
                 options,
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -569,6 +1713,144 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// Make all the comments in the worksheet visible when the file is opened. By default comments are only shown when the cell is hovered:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_show_comments-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_comment(0, 0, "This comment is always visible")?;
+    /// worksheet.show_comments();
+    /// worksheet.set_comments_author("Reviewer")?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Individual comments can still be hidden with the `visible` field of [`CommentOptions`].
+    pub fn show_comments(&mut self) {
+        unsafe {
+            libxlsxwriter_sys::worksheet_show_comments(self.worksheet);
+        }
+    }
+
+    /// Set the default author of the comments in the worksheet. The author of a single comment can be set with the `author` field of [`CommentOptions`],
+    /// which takes precedence. See [`Worksheet::show_comments()`] for an example and [`Workbook::set_comments_author()`] to set the author of all worksheets.
+    pub fn set_comments_author(&mut self, author: &str) -> Result<(), XlsxError> {
+        // libxlsxwriter copies the author, so the CString only has to outlive the call.
+        let author = to_c_string(author)?;
+        unsafe {
+            libxlsxwriter_sys::worksheet_set_comments_author(self.worksheet, author.as_ptr());
+        }
+        Ok(())
+    }
+
+    /// This function writes any value that can be converted into a [`CellValue`] by calling the matching `write_*()` function:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write(0, 0, "Total", None)?;
+    /// worksheet.write(0, 1, 42, None)?;
+    /// worksheet.write(0, 2, true, None)?;
+    /// worksheet.write(0, 3, CellValue::Formula("=B1*2".to_string()), None)?;
+    /// worksheet.write(0, 4, DateTime::new(2021, 1, 1, 0, 0, 0.), None)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Strings are always written as strings. Use `CellValue::Formula` to write a formula.
+    ///
+    /// A NaN or infinite number returns an error, or is written as a blank cell after [`Workbook::set_non_finite_as_blank()`].
+    pub fn write<V: Into<CellValue>>(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        value: V,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        self.write_cell_value(row, col, &value.into(), format)
+    }
+
+    /// This function writes a value to a cell and attaches a comment to the same cell:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_with_comment-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_with_comment(
+    ///     0,
+    ///     0,
+    ///     CellValue::Number(0.25),
+    ///     None,
+    ///     "Estimated from the 2019 survey",
+    /// )?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// The value is written first. If writing it fails, the comment is not added.
+    pub fn write_with_comment(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        value: CellValue,
+        format: Option<&Format>,
+        comment: &str,
+    ) -> Result<(), XlsxError> {
+        self.write_cell_value(row, col, &value, format)?;
+        self.write_comment(row, col, comment)
+    }
+
+    /// This function writes a value to a cell like [`Worksheet::write_with_comment()`] without the comment, and reports whether the cell had already been written. It returns `Ok(true)` when an existing cell was overwritten:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_try_write-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// assert!(!worksheet.try_write(0, 0, CellValue::Number(1.), None)?);
+    /// assert!(worksheet.try_write(0, 0, CellValue::Number(2.), None)?);
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// ### Note
+    /// A blank cell without a format is ignored by Excel and is therefore not reported as written. In `constant_memory` mode rows that have already been flushed to disk are not found either.
+    pub fn try_write(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        value: CellValue,
+        format: Option<&Format>,
+    ) -> Result<bool, XlsxError> {
+        let overwritten = self.is_cell_written(row, col);
+        self.write_cell_value(row, col, &value, format)?;
+        Ok(overwritten)
+    }
+
+    fn is_cell_written(&self, row: WorksheetRow, col: WorksheetCol) -> bool {
+        unsafe {
+            let row = libxlsxwriter_sys::lxw_worksheet_find_row(self.worksheet, row);
+            !row.is_null() && !libxlsxwriter_sys::lxw_worksheet_find_cell_in_row(row, col).is_null()
+        }
+    }
+
+    fn write_cell_value(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        value: &CellValue,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        match value {
+            CellValue::Number(x) if !x.is_finite() && self._workbook.non_finite_as_blank.get() => {
+                self.write_blank(row, col, format)
+            }
+            CellValue::Number(x) => self.write_number(row, col, *x, format),
+            CellValue::String(x) => self.write_string(row, col, x, format),
+            CellValue::Bool(x) => self.write_boolean(row, col, *x, format),
+            CellValue::Formula(x) => self.write_formula(row, col, x, format),
+            CellValue::DateTime(x) => self.write_datetime(row, col, x, format),
+            CellValue::Blank => self.write_blank(row, col, format),
+        }
+    }
+
     /// This function writes numeric types to the cell specified by row and column:
     /// ```rust
     /// # use xlsxwriter::*;
@@ -591,7 +1873,7 @@ impl<'a> Worksheet<'a> {
     /// # fn main() -> Result<(), XlsxError> {
     /// # let workbook = Workbook::new("test-worksheet_write_number-2.xlsx");
     /// let format = workbook.add_format()
-    ///     .set_num_format("$#,##0.00");
+    ///     .set_num_format("$#,##0.00")?;
     /// # let mut worksheet = workbook.add_worksheet(None)?;
     /// worksheet.write_number(0, 0, 1234.567, Some(&format))?;
     /// # workbook.close()
@@ -600,7 +1882,19 @@ impl<'a> Worksheet<'a> {
     /// ![Result Image](https://github.com/informationsea/xlsxwriter-rs/raw/master/images/test-worksheet-write_number-2.png)
     ///
     /// ### Note
-    /// Excel doesn't support NaN, Inf or -Inf as a number value. If you are writing data that contains these values then your application should convert them to a string or handle them in some other way.
+    /// Excel doesn't support NaN, Inf or -Inf as a number value. An error is returned and nothing is written if `number` isn't finite.
+    /// If you are writing data that contains these values then your application should convert them to a string or handle them in some other way,
+    /// e.g. with [`Worksheet::write_number_lossy()`]. [`Worksheet::write()`] can write them as blank cells instead, see
+    /// [`Workbook::set_non_finite_as_blank()`].
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_number-3.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// assert!(worksheet.write_number(0, 0, f64::NAN, None).is_err());
+    /// # workbook.close()
+    /// # }
+    /// ```
     pub fn write_number(
         &mut self,
         row: WorksheetRow,
@@ -608,6 +1902,9 @@ impl<'a> Worksheet<'a> {
         number: f64,
         format: Option<&Format>,
     ) -> Result<(), XlsxError> {
+        if !number.is_finite() {
+            return Err(XlsxError::new(crate::error::NON_FINITE_NUMBER).at_cell(row, col));
+        }
         unsafe {
             let result = libxlsxwriter_sys::worksheet_write_number(
                 self.worksheet,
@@ -617,6 +1914,7 @@ impl<'a> Worksheet<'a> {
                 format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                self.track_width(col, || number_width(number));
                 Ok(())
             } else {
                 Err(XlsxError::new(result))
@@ -624,6 +1922,38 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// This function is the same as `write_number()`, except that NaN, Inf and -Inf are written as the strings `NaN`, `Inf` and `-Inf`
+    /// instead of returning an error:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_number_lossy-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_number_lossy(0, 0, 1.5, None)?;
+    /// worksheet.write_number_lossy(1, 0, f64::NAN, None)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Excel shows these cells as left aligned text, number formats don't apply to them, and functions such as `SUM()` skip them,
+    /// while arithmetic like `=A2+1` returns `#VALUE!`.
+    pub fn write_number_lossy(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        number: f64,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        if number.is_nan() {
+            self.write_string(row, col, "NaN", format)
+        } else if number == f64::INFINITY {
+            self.write_string(row, col, "Inf", format)
+        } else if number == f64::NEG_INFINITY {
+            self.write_string(row, col, "-Inf", format)
+        } else {
+            self.write_number(row, col, number, format)
+        }
+    }
+
     /// This function writes a string to the cell specified by row and column:
     /// ```rust
     /// # use xlsxwriter::*;
@@ -669,20 +1999,96 @@ impl<'a> Worksheet<'a> {
         text: &str,
         format: Option<&Format>,
     ) -> Result<(), XlsxError> {
-        unsafe {
-            let result = libxlsxwriter_sys::worksheet_write_string(
+        let result = with_c_str(text, |text| unsafe {
+            libxlsxwriter_sys::worksheet_write_string(
                 self.worksheet,
                 row,
                 col,
-                CString::new(text).unwrap().as_c_str().as_ptr(),
+                text,
                 format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
-            );
-            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
-                Ok(())
-            } else {
-                Err(XlsxError::new(result))
-            }
+            )
+        })?;
+        if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+            self.track_width(col, || text_width(text));
+            Ok(())
+        } else {
+            Err(XlsxError::new(result))
+        }
+    }
+
+    /// This function writes a slice of numbers to a row, starting at `first_col`:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_row_numbers-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_row_strings(0, 0, &["Q1", "Q2", "Q3", "Q4"], None)?;
+    /// worksheet.write_row_numbers(1, 0, &[10.5, 20., 15.25, 30.], None)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Writing stops at the first cell that can't be written. The returned error holds the position of that cell, see [`XlsxError::cell()`].
+    pub fn write_row_numbers(
+        &mut self,
+        row: WorksheetRow,
+        first_col: WorksheetCol,
+        values: &[f64],
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        for (i, value) in values.iter().enumerate() {
+            let col = offset_col(first_col, i);
+            self.write_number(row, col, *value, format)
+                .map_err(|e| e.at_cell(row, col))?;
         }
+        Ok(())
+    }
+
+    /// This function writes a slice of numbers to a column, starting at `first_row`. See [`Worksheet::write_row_numbers()`].
+    pub fn write_column_numbers(
+        &mut self,
+        first_row: WorksheetRow,
+        col: WorksheetCol,
+        values: &[f64],
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        for (i, value) in values.iter().enumerate() {
+            let row = offset_row(first_row, i);
+            self.write_number(row, col, *value, format)
+                .map_err(|e| e.at_cell(row, col))?;
+        }
+        Ok(())
+    }
+
+    /// This function writes a slice of strings to a row, starting at `first_col`. See [`Worksheet::write_row_numbers()`].
+    pub fn write_row_strings(
+        &mut self,
+        row: WorksheetRow,
+        first_col: WorksheetCol,
+        values: &[&str],
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        for (i, value) in values.iter().enumerate() {
+            let col = offset_col(first_col, i);
+            self.write_string(row, col, value, format)
+                .map_err(|e| e.at_cell(row, col))?;
+        }
+        Ok(())
+    }
+
+    /// This function writes a slice of strings to a column, starting at `first_row`. See [`Worksheet::write_row_numbers()`].
+    pub fn write_column_strings(
+        &mut self,
+        first_row: WorksheetRow,
+        col: WorksheetCol,
+        values: &[&str],
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        for (i, value) in values.iter().enumerate() {
+            let row = offset_row(first_row, i);
+            self.write_string(row, col, value, format)
+                .map_err(|e| e.at_cell(row, col))?;
+        }
+        Ok(())
     }
 
     /// This function writes a formula or function to the cell specified by row and column:
@@ -720,6 +2126,8 @@ impl<'a> Worksheet<'a> {
     /// # }
     /// ```
     /// See also [Working with Formulas](https://libxlsxwriter.github.io/working_with_formulas.html).
+    ///
+    /// An error is returned if the formula is empty or only contains whitespace. This also applies to `write_formula_num()`, `write_formula_str()` and `write_array_formula()`.
     pub fn write_formula(
         &mut self,
         row: WorksheetRow,
@@ -727,12 +2135,13 @@ impl<'a> Worksheet<'a> {
         formula: &str,
         format: Option<&Format>,
     ) -> Result<(), XlsxError> {
+        check_formula(formula)?;
         unsafe {
             let result = libxlsxwriter_sys::worksheet_write_formula(
                 self.worksheet,
                 row,
                 col,
-                CString::new(formula).unwrap().as_c_str().as_ptr(),
+                to_c_string(formula)?.as_ptr(),
                 format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -766,6 +2175,7 @@ impl<'a> Worksheet<'a> {
     /// # workbook.close()
     /// # }
     /// ```
+    /// An error is returned if `first_row` is greater than `last_row` or `first_col` is greater than `last_col`. The size of the range isn't checked against the result of the formula since the formula isn't evaluated.
     pub fn write_array_formula(
         &mut self,
         first_row: WorksheetRow,
@@ -775,6 +2185,8 @@ impl<'a> Worksheet<'a> {
         formula: &str,
         format: Option<&Format>,
     ) -> Result<(), XlsxError> {
+        check_range(first_row, first_col, last_row, last_col)?;
+        check_formula(formula)?;
         unsafe {
             let result = libxlsxwriter_sys::worksheet_write_array_formula(
                 self.worksheet,
@@ -782,7 +2194,55 @@ impl<'a> Worksheet<'a> {
                 first_col,
                 last_row,
                 last_col,
-                CString::new(formula).unwrap().as_c_str().as_ptr(),
+                to_c_string(formula)?.as_ptr(),
+                format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
+            );
+            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                Ok(())
+            } else {
+                Err(XlsxError::new(result))
+            }
+        }
+    }
+
+    /// This function can be used to write a date or time to the cell specified by row and column:
+    /// ```rust
+    /// use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_datetime-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let datetime = DateTime::new(2013, 2, 28, 12, 0, 0.0);
+    /// let datetime_format = workbook.add_format()
+    ///     .set_num_format("mmm d yyyy hh:mm AM/PM")?;
+    /// worksheet.write_datetime(1, 0, &datetime, Some(&datetime_format))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// ![Result Image](https://github.com/informationsea/xlsxwriter-rs/raw/master/images/test-worksheet-write_datetime-1.png)
+    ///
+    /// The `format` parameter should be used to apply formatting to the cell using a [Format](struct.Format.html) object as shown above. Without a date format the datetime will appear as a number only.
+    ///
+    /// See [Working with Dates and Times](https://libxlsxwriter.github.io/working_with_dates.html) for more information about handling dates and times in libxlsxwriter.
+    ///
+    /// ### Note
+    /// Excel stores a date as a serial number of days, so the fractional seconds of `DateTime.second` are converted to a fraction of a day and
+    /// can't be stored more precisely than about a microsecond. Excel only shows times to the millisecond, and number formats without fractional
+    /// seconds such as `hh:mm:ss` round the time to the nearest second, so `12:00:00.999` is displayed as `12:00:01`. Use
+    /// [`Worksheet::write_datetime_seconds()`] to drop the fractional seconds instead.
+    pub fn write_datetime(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        datetime: &DateTime,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        unsafe {
+            let mut xls_datetime: libxlsxwriter_sys::lxw_datetime = datetime.into();
+            let result = libxlsxwriter_sys::worksheet_write_datetime(
+                self.worksheet,
+                row,
+                col,
+                &mut xls_datetime,
                 format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -793,46 +2253,77 @@ impl<'a> Worksheet<'a> {
         }
     }
 
-    /// This function can be used to write a date or time to the cell specified by row and column:
+    /// This function is the same as `write_datetime()` but truncates the fractional part of the seconds, so that the time is stored as whole seconds:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_datetime_seconds-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let datetime = DateTime::new(2013, 2, 28, 12, 0, 0.999);
+    /// let datetime_format = workbook.add_format()
+    ///     .set_num_format("yyyy-mm-dd hh:mm:ss")?;
+    /// // Displayed as 2013-02-28 12:00:00 instead of 2013-02-28 12:00:01.
+    /// worksheet.write_datetime_seconds(1, 0, &datetime, Some(&datetime_format))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn write_datetime_seconds(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        datetime: &DateTime,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        self.write_datetime(row, col, &datetime.with_whole_seconds(), format)
+    }
+
+    /// Write the date of `datetime` to a cell. The time is dropped, so the cell holds a whole number of days:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_date-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let date_format = workbook.add_format().set_num_format("yyyy-mm-dd")?;
+    /// worksheet.write_date(0, 0, &DateTime::from_date(2013, 2, 28), Some(&date_format))?;
+    /// // Written as 2013-02-28, the time is ignored.
+    /// worksheet.write_date(1, 0, &DateTime::new(2013, 2, 28, 18, 30, 0.), Some(&date_format))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// As with `write_datetime()`, the cell needs a date format to be displayed as a date.
+    pub fn write_date(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        datetime: &DateTime,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        let date = DateTime::from_date(datetime.year, datetime.month, datetime.day);
+        self.write_datetime(row, col, &date, format)
+    }
+
+    /// Write the time of `datetime` to a cell. The date is dropped, so the cell holds a fraction of a day:
     /// ```rust
-    /// use xlsxwriter::*;
+    /// # use xlsxwriter::*;
     /// # fn main() -> Result<(), XlsxError> {
-    /// # let workbook = Workbook::new("test-worksheet_write_datetime-1.xlsx");
+    /// # let workbook = Workbook::new("test-worksheet_write_time-1.xlsx");
     /// # let mut worksheet = workbook.add_worksheet(None)?;
-    /// let datetime = DateTime::new(2013, 2, 28, 12, 0, 0.0);
-    /// let datetime_format = workbook.add_format()
-    ///     .set_num_format("mmm d yyyy hh:mm AM/PM");
-    /// worksheet.write_datetime(1, 0, &datetime, Some(&datetime_format))?;
+    /// let time_format = workbook.add_format().set_num_format("hh:mm:ss")?;
+    /// worksheet.write_time(0, 0, &DateTime::from_time(18, 30, 0.), Some(&time_format))?;
     /// # workbook.close()
     /// # }
     /// ```
-    /// ![Result Image](https://github.com/informationsea/xlsxwriter-rs/raw/master/images/test-worksheet-write_datetime-1.png)
-    ///
-    /// The `format` parameter should be used to apply formatting to the cell using a [Format](struct.Format.html) object as shown above. Without a date format the datetime will appear as a number only.
-    ///
-    /// See [Working with Dates and Times](https://libxlsxwriter.github.io/working_with_dates.html) for more information about handling dates and times in libxlsxwriter.
-    pub fn write_datetime(
+    /// Use a time format such as `hh:mm` for the cell. With a date format Excel shows a time only value as the day before
+    /// 1900-01-01, and a date and time value with a date only format shows no time at all.
+    pub fn write_time(
         &mut self,
         row: WorksheetRow,
         col: WorksheetCol,
         datetime: &DateTime,
         format: Option<&Format>,
     ) -> Result<(), XlsxError> {
-        unsafe {
-            let mut xls_datetime: libxlsxwriter_sys::lxw_datetime = datetime.into();
-            let result = libxlsxwriter_sys::worksheet_write_datetime(
-                self.worksheet,
-                row,
-                col,
-                &mut xls_datetime,
-                format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
-            );
-            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
-                Ok(())
-            } else {
-                Err(XlsxError::new(result))
-            }
-        }
+        let time = DateTime::from_time(datetime.hour, datetime.min, datetime.second);
+        self.write_datetime(row, col, &time, format)
     }
 
     /// This function is used to write a URL/hyperlink to a worksheet cell specified by row and column.
@@ -901,6 +2392,12 @@ impl<'a> Worksheet<'a> {
     /// # workbook.close()
     /// # }
     /// ```
+    ///
+    /// The URL is checked before it is written. An error is returned if it doesn't start with a scheme such as `https://`, `mailto:`,
+    /// `internal:` or `external:`, if an `internal:` link has no sheet name, or if there is nothing after the scheme.
+    ///
+    /// If `format` is `None` the workbook's default hyperlink format (blue underlined text) is used. That format is created once
+    /// per workbook and shared by every hyperlink written without an explicit format, so writing many URLs doesn't add new cell formats.
     pub fn write_url(
         &mut self,
         row: WorksheetRow,
@@ -908,12 +2405,13 @@ impl<'a> Worksheet<'a> {
         url: &str,
         format: Option<&Format>,
     ) -> Result<(), XlsxError> {
+        check_url(url).map_err(|e| e.at_cell(row, col))?;
         unsafe {
             let result = libxlsxwriter_sys::worksheet_write_url(
                 self.worksheet,
                 row,
                 col,
-                CString::new(url).unwrap().as_c_str().as_ptr(),
+                to_c_string(url)?.as_ptr(),
                 format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -924,6 +2422,32 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// This function writes a hyperlink to an external file. It is a shortcut for `write_url()` with an `external:` link which accepts paths written in the style of the current platform:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_write_external_link-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_external_link(0, 0, "../reports/2021.xlsx#Summary!A1", None)?;
+    /// worksheet.write_external_link(1, 0, "file:///C:/temp/foo.xlsx", None)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Excel stores external links with Windows separators, so every `/` in the part of `path` before the `#` anchor is
+    /// replaced with `\`, unless `path` is an absolute POSIX path such as `/home/user/foo.xlsx`. A `file://` URL is
+    /// percent-decoded and converted to a path: `file:///C:/temp/foo.xlsx` becomes `C:\temp\foo.xlsx`,
+    /// `file://server/share/foo.xlsx` becomes the network path `\\server\share\foo.xlsx` and `file:///tmp/my%20file.xlsx`
+    /// becomes `/tmp/my file.xlsx`.
+    pub fn write_external_link(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        path: &str,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        self.write_url(row, col, &external_link_url(path), format)
+    }
+
     /// Write an Excel boolean to the cell specified by row and column:
     /// ```rust
     /// # use xlsxwriter::*;
@@ -1029,12 +2553,13 @@ impl<'a> Worksheet<'a> {
         format: Option<&Format>,
         number: f64,
     ) -> Result<(), XlsxError> {
+        check_formula(formula)?;
         unsafe {
             let result = libxlsxwriter_sys::worksheet_write_formula_num(
                 self.worksheet,
                 row,
                 col,
-                CString::new(formula).unwrap().as_c_str().as_ptr(),
+                to_c_string(formula)?.as_ptr(),
                 format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
                 number,
             );
@@ -1063,7 +2588,7 @@ impl<'a> Worksheet<'a> {
     /// why/when these functions are required.
     ///
     /// One place where the worksheet_write_formula_str() function may be required is to specify an empty result which
-    /// will force a recalculation of the formula when loaded in LibreOffice.
+    /// will force a recalculation of the formula when loaded in LibreOffice. An empty result is allowed but an empty formula is still an error.
     #[allow(clippy::too_many_arguments)]
     pub fn write_formula_str(
         &mut self,
@@ -1073,14 +2598,15 @@ impl<'a> Worksheet<'a> {
         format: Option<&Format>,
         result: &str,
     ) -> Result<(), XlsxError> {
+        check_formula(formula)?;
         unsafe {
             let result = libxlsxwriter_sys::worksheet_write_formula_str(
                 self.worksheet,
                 row,
                 col,
-                CString::new(formula).unwrap().as_c_str().as_ptr(),
+                to_c_string(formula)?.as_ptr(),
                 format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
-                CString::new(result).unwrap().as_c_str().as_ptr(),
+                to_c_string(result)?.as_ptr(),
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
                 Ok(())
@@ -1129,7 +2655,10 @@ impl<'a> Worksheet<'a> {
     /// This in then converted to the tuple fragments shown in the example above. For the default format we use None.
     ///
     /// ### Note
-    ///  Excel doesn't allow the use of two consecutive formats in a rich string or an empty string fragment. For either of these conditions a warning is raised and the input to `worksheet.write_rich_string()` is ignored.
+    ///  Excel doesn't allow the use of two consecutive formats in a rich string or an empty string fragment. libxlsxwriter only raises a warning for these,
+    ///  so `write_rich_string()` checks the fragments first and returns an error if `text` is empty or if any fragment is an empty string.
+    ///  [`XlsxError::fragment()`] gives the index of the offending fragment. Since every format is paired with a fragment here, a format
+    ///  followed directly by another format shows up as an empty fragment.
     pub fn write_rich_string(
         &mut self,
         row: WorksheetRow,
@@ -1137,23 +2666,26 @@ impl<'a> Worksheet<'a> {
         text: &[(&str, Option<&Format>)],
         format: Option<&Format>,
     ) -> Result<(), XlsxError> {
-        let mut c_str: Vec<Vec<u8>> = text
+        if text.is_empty() {
+            return Err(XlsxError::new(crate::error::INVALID_RICH_STRING).at_cell(row, col));
+        }
+        if let Some(i) = text.iter().position(|x| x.0.is_empty()) {
+            return Err(XlsxError::new(crate::error::INVALID_RICH_STRING)
+                .at_cell(row, col)
+                .at_fragment(i));
+        }
+        let c_str: Vec<CString> = text
             .iter()
-            .map(|x| {
-                CString::new(x.0)
-                    .unwrap()
-                    .as_c_str()
-                    .to_bytes_with_nul()
-                    .to_vec()
-            })
-            .collect();
+            .map(|x| to_c_string(x.0))
+            .collect::<Result<_, _>>()?;
 
+        // libxlsxwriter only reads the fragments, so the CStrings can be passed without copying them into mutable buffers.
         let mut rich_text: Vec<_> = text
             .iter()
-            .zip(c_str.iter_mut())
+            .zip(c_str.iter())
             .map(|(x, y)| libxlsxwriter_sys::lxw_rich_string_tuple {
                 format: x.1.map(|z| z.format).unwrap_or(std::ptr::null_mut()),
-                string: y.as_mut_ptr() as *mut c_char,
+                string: y.as_ptr() as *mut c_char,
             })
             .collect();
         let mut rich_text_ptr: Vec<*mut libxlsxwriter_sys::lxw_rich_string_tuple> = rich_text
@@ -1199,6 +2731,34 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// Set the height and format of every row from `first_row` to `last_row`, both inclusive. It stops at the first row that can't be set.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_set_row_range-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let striped = workbook.add_format().set_bg_color(FormatColor::Silver);
+    /// worksheet.set_row_range(1, 10, 20., Some(&striped))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// An error is returned if `first_row` is after `last_row`.
+    pub fn set_row_range(
+        &mut self,
+        first_row: WorksheetRow,
+        last_row: WorksheetRow,
+        height: f64,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        if first_row > last_row {
+            return Err(XlsxError::new(crate::error::REVERSED_RANGE));
+        }
+        for row in first_row..=last_row {
+            self.set_row(row, height, format)?;
+        }
+        Ok(())
+    }
+
     pub fn set_row_opt(
         &mut self,
         row: WorksheetRow,
@@ -1292,6 +2852,103 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// Start remembering the widest string or number written to each column of this worksheet, for [`Worksheet::autofit()`].
+    /// Tracking is off by default so that writing cells doesn't pay for it, call this before writing the data.
+    pub fn enable_autofit(&mut self) {
+        self.autofit_enabled = true;
+        self._workbook
+            .text_widths
+            .borrow_mut()
+            .entry(self.worksheet)
+            .or_default();
+    }
+
+    /// Set the width of every column that has been written to, based on the longest string or number written to it with
+    /// `write_string()`, `write_number()` or functions built on them such as `write()` after [`Worksheet::enable_autofit()`]:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_autofit-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.enable_autofit();
+    /// worksheet.write_string(0, 0, "Product", None)?;
+    /// worksheet.write_string(1, 0, "An unusually long product name", None)?;
+    /// worksheet.write_number(1, 1, 1234567.5, None)?;
+    /// worksheet.autofit()?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// ### Note
+    /// libxlsxwriter doesn't measure text, so this is an approximation that counts characters. It doesn't know about the font,
+    /// bold text, number formats or wide characters, and formulas, dates and rich strings aren't measured. Columns are only widened,
+    /// never narrowed below their current width, and keep the format set with [`Worksheet::set_column()`]. Call `autofit()` after
+    /// writing the data. Without `enable_autofit()` nothing has been tracked and `autofit()` leaves the columns unchanged.
+    pub fn autofit(&mut self) -> Result<(), XlsxError> {
+        let widths = self
+            ._workbook
+            .text_widths
+            .borrow()
+            .get(&self.worksheet)
+            .cloned()
+            .unwrap_or_default();
+        for (col, chars) in widths.into_iter().enumerate() {
+            if chars == 0 {
+                continue;
+            }
+            let col = col as WorksheetCol;
+            // Roughly one width unit per character of the default font, plus padding.
+            let width = (chars as f64 * 1.1 + 1.).min(255.);
+            let (current_width, format) = unsafe {
+                let worksheet = &*self.worksheet;
+                let current_width =
+                    if col < worksheet.col_sizes_max && !worksheet.col_sizes.is_null() {
+                        *worksheet.col_sizes.add(col.into())
+                    } else {
+                        // Excel's default column width. LXW_DEF_COL_WIDTH holds the default row height instead.
+                        8.43
+                    };
+                let format = if col < worksheet.col_formats_max && !worksheet.col_formats.is_null()
+                {
+                    *worksheet.col_formats.add(col.into())
+                } else {
+                    std::ptr::null_mut()
+                };
+                (current_width, format)
+            };
+            // A zero width hides the column, keep it hidden.
+            if current_width > 0. && width > current_width {
+                unsafe {
+                    let result = libxlsxwriter_sys::worksheet_set_column(
+                        self.worksheet,
+                        col,
+                        col,
+                        width,
+                        format,
+                    );
+                    if result != libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                        return Err(XlsxError::new(result));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Remember the widest value written to `col` for `autofit()`, if `enable_autofit()` was called for this worksheet.
+    fn track_width(&self, col: WorksheetCol, width: impl FnOnce() -> usize) {
+        if !self.autofit_enabled {
+            return;
+        }
+        let mut text_widths = self._workbook.text_widths.borrow_mut();
+        if let Some(widths) = text_widths.get_mut(&self.worksheet) {
+            let col = usize::from(col);
+            if widths.len() <= col {
+                widths.resize(col + 1, 0);
+            }
+            widths[col] = widths[col].max(width());
+        }
+    }
+
     pub fn set_column_opt(
         &mut self,
         first_col: WorksheetCol,
@@ -1371,12 +3028,17 @@ impl<'a> Worksheet<'a> {
     /// # fn main() -> Result<(), XlsxError> {
     /// # let workbook = Workbook::new("test-worksheet_insert_image-1.xlsx");
     /// # let mut worksheet = workbook.add_worksheet(None)?;
-    /// worksheet.insert_image(2, 1, "../images/simple1.png")?;
+    /// let size = worksheet.insert_image(2, 1, "../images/simple1.png")?;
+    /// assert_eq!(size, Some(ImageDimensions { width: 425, height: 165 }));
     /// # workbook.close()
     /// # }
     /// ```
     /// ![Result Image](https://github.com/informationsea/xlsxwriter-rs/raw/master/images/test-worksheet-insert_image-1.png)
     ///
+    /// The natural size of the image in pixels is returned, so that further images can be placed without overlapping it. The size is
+    /// read from the image header after the image has been inserted, and is `None` if the header can't be parsed. The image is
+    /// still in the worksheet in that case.
+    ///
     /// The Worksheet.insert_image_opt() function takes additional optional parameters to position and scale the image, see below.
     ///
     /// ### Note
@@ -1388,20 +3050,23 @@ impl<'a> Worksheet<'a> {
         row: WorksheetRow,
         col: WorksheetCol,
         filename: &str,
-    ) -> Result<(), XlsxError> {
+    ) -> Result<Option<ImageDimensions>, XlsxError> {
         unsafe {
             let result = libxlsxwriter_sys::worksheet_insert_image(
                 self.worksheet,
                 row,
                 col,
-                CString::new(filename).unwrap().as_c_str().as_ptr(),
+                to_c_string(filename)?.as_ptr(),
             );
-            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
-                Ok(())
-            } else {
-                Err(XlsxError::new(result))
+            if result != libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                return Err(XlsxError::new(result));
             }
         }
+        // libxlsxwriter has already validated the image, but it doesn't expose the size it read. Only the header is read again.
+        Ok(crate::image::image_file_dimensions(filename)
+            .ok()
+            .flatten()
+            .map(|(width, height)| ImageDimensions { width, height }))
     }
 
     /// This function is like Worksheet.insert_image() function except that it takes an optional `ImageOptions` struct to scale and position the image:
@@ -1418,6 +3083,7 @@ impl<'a> Worksheet<'a> {
     ///         y_offset: 30,
     ///         x_scale: 0.5,
     ///         y_scale: 0.5,
+    ///         ..Default::default()
     ///     }
     /// )?;
     /// # workbook.close()
@@ -1425,6 +3091,27 @@ impl<'a> Worksheet<'a> {
     /// ```
     /// ![Result Image](https://github.com/informationsea/xlsxwriter-rs/raw/master/images/test-worksheet-insert_image_opt-1.png)
     ///
+    /// Set `url` to make the image a hyperlink, and `tip` to show a tooltip over it:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_insert_image_opt-2.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.insert_image_opt(
+    ///     0,
+    ///     0,
+    ///     "../images/simple1.png",
+    ///     &ImageOptions {
+    ///         url: Some("https://github.com/informationsea/xlsxwriter-rs".to_string()),
+    ///         tip: Some("Project page".to_string()),
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// An invalid `url` returns the same errors as [`Worksheet::write_url()`].
+    ///
     /// ### Note
     /// See the notes about row scaling and BMP images in Worksheet.insert_image() above.
     pub fn insert_image_opt(
@@ -1434,13 +3121,80 @@ impl<'a> Worksheet<'a> {
         filename: &str,
         opt: &ImageOptions,
     ) -> Result<(), XlsxError> {
-        let mut opt_struct = opt.into();
+        let filename = to_c_string(filename)?;
+        let result = with_image_options(opt, |opt_struct| unsafe {
+            libxlsxwriter_sys::worksheet_insert_image_opt(
+                self.worksheet,
+                row,
+                col,
+                filename.as_ptr(),
+                opt_struct,
+            )
+        })
+        .map_err(|e| e.at_cell(row, col))?;
+        if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+            Ok(())
+        } else {
+            Err(XlsxError::new(result))
+        }
+    }
+
+    /// This function inserts a form button into the worksheet. The button is usually tied to a VBA macro, so the workbook also
+    /// needs a VBA project added with [`Workbook::add_vba_project()`] and a `.xlsm` file name:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_insert_button-1.xlsm");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.insert_button(
+    ///     1,
+    ///     1,
+    ///     &ButtonOptions {
+    ///         caption: Some("Press Me".to_string()),
+    ///         macro_name: Some("say_hello".to_string()),
+    ///         width: 80,
+    ///         height: 30,
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn insert_button(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        options: &ButtonOptions,
+    ) -> Result<(), XlsxError> {
+        // libxlsxwriter copies the strings, so they only have to outlive the call.
+        let caption = options.caption.as_deref().map(to_c_string).transpose()?;
+        let macro_name = options.macro_name.as_deref().map(to_c_string).transpose()?;
+        let description = options
+            .description
+            .as_deref()
+            .map(to_c_string)
+            .transpose()?;
+        let as_ptr = |x: &Option<CString>| {
+            x.as_ref()
+                .map(|y| y.as_ptr() as *mut c_char)
+                .unwrap_or(std::ptr::null_mut())
+        };
+        let mut opt_struct = libxlsxwriter_sys::lxw_button_options {
+            caption: as_ptr(&caption),
+            macro_: as_ptr(&macro_name),
+            description: as_ptr(&description),
+            width: options.width,
+            height: options.height,
+            x_scale: 0.,
+            y_scale: 0.,
+            x_offset: 0,
+            y_offset: 0,
+        };
         unsafe {
-            let result = libxlsxwriter_sys::worksheet_insert_image_opt(
+            let result = libxlsxwriter_sys::worksheet_insert_button(
                 self.worksheet,
                 row,
                 col,
-                CString::new(filename).unwrap().as_c_str().as_ptr(),
                 &mut opt_struct,
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -1451,6 +3205,103 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// This function inserts an image centered in a single cell. The image is scaled down, keeping its aspect ratio, if it is larger than the cell:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_insert_image_centered-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.set_column(1, 1, 30., None)?;
+    /// worksheet.set_row(2, 60., None)?;
+    /// worksheet.insert_image_centered(2, 1, "../images/simple1.png")?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// The cell size is taken from `set_column()` and `set_row()`, so they have to be called before this function. The image is assumed to have the default resolution of 96 DPI.
+    ///
+    /// An error is returned if the file doesn't exist or can't be read, and if its format isn't recognized.
+    pub fn insert_image_centered(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        filename: &str,
+    ) -> Result<(), XlsxError> {
+        let image_size = image_file_size(filename)?;
+        let options = centered_image_options(image_size, self.cell_size_pixels(row, col));
+        self.insert_image_opt(row, col, filename, &options)
+    }
+
+    /// Width and height of a cell in pixels, see `column_width_pixels()` and `row_height_pixels()`.
+    pub(crate) fn cell_size_pixels(&self, row: WorksheetRow, col: WorksheetCol) -> (u32, u32) {
+        unsafe {
+            let worksheet = &*self.worksheet;
+            let width = if col < worksheet.col_sizes_max && !worksheet.col_sizes.is_null() {
+                column_width_pixels(*worksheet.col_sizes.add(col.into()))
+            } else {
+                LXW_DEF_COL_WIDTH_PIXELS
+            };
+            let row = libxlsxwriter_sys::lxw_worksheet_find_row(self.worksheet, row);
+            let height = if row.is_null() {
+                worksheet.default_row_height
+            } else if (*row).hidden != 0 {
+                0.
+            } else {
+                (*row).height
+            };
+            (width, row_height_pixels(height))
+        }
+    }
+
+    /// This function inserts an image scaled to the range from `(first_row, first_col)` to `(last_row, last_col)`. The [`FitMode`]
+    /// chooses whether the image is stretched to the range, fits inside it or covers it:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_insert_image_fit_to_range-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.insert_image_fit_to_range(1, 1, 10, 4, "../images/simple1.png", FitMode::Contain)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Like [`Worksheet::insert_image_centered()`], the range size is taken from `set_column()` and `set_row()`, so they have to be
+    /// called before this function, and the image is assumed to have the default resolution of 96 DPI. An error is returned if the
+    /// file doesn't exist or can't be read, and if its format isn't recognized.
+    pub fn insert_image_fit_to_range(
+        &mut self,
+        first_row: WorksheetRow,
+        first_col: WorksheetCol,
+        last_row: WorksheetRow,
+        last_col: WorksheetCol,
+        filename: &str,
+        mode: FitMode,
+    ) -> Result<(), XlsxError> {
+        check_range(first_row, first_col, last_row, last_col)?;
+        let image_size = image_file_size(filename)?;
+        let options = fit_image_options(
+            image_size,
+            self.range_size_pixels(first_row, first_col, last_row, last_col),
+            mode,
+        );
+        self.insert_image_opt(first_row, first_col, filename, &options)
+    }
+
+    /// Width and height of a range of cells in pixels, see `cell_size_pixels()`.
+    pub(crate) fn range_size_pixels(
+        &self,
+        first_row: WorksheetRow,
+        first_col: WorksheetCol,
+        last_row: WorksheetRow,
+        last_col: WorksheetCol,
+    ) -> (u32, u32) {
+        let width = (first_col..=last_col)
+            .map(|col| self.cell_size_pixels(first_row, col).0)
+            .sum();
+        let height = (first_row..=last_row)
+            .map(|row| self.cell_size_pixels(row, first_col).1)
+            .sum();
+        (width, height)
+    }
+
     /// This function can be used to insert a image into a worksheet from a memory buffer:
     /// ```rust
     /// # use xlsxwriter::*;
@@ -1492,14 +3343,83 @@ impl<'a> Worksheet<'a> {
         buffer: &[u8],
         opt: &ImageOptions,
     ) -> Result<(), XlsxError> {
-        let mut opt_struct = opt.into();
-        unsafe {
-            let result = libxlsxwriter_sys::worksheet_insert_image_buffer_opt(
+        let result = with_image_options(opt, |opt_struct| unsafe {
+            libxlsxwriter_sys::worksheet_insert_image_buffer_opt(
                 self.worksheet,
                 row,
                 col,
                 buffer.as_ptr(),
                 buffer.len(),
+                opt_struct,
+            )
+        })
+        .map_err(|e| e.at_cell(row, col))?;
+        if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+            Ok(())
+        } else {
+            Err(XlsxError::new(result))
+        }
+    }
+
+    pub fn insert_chart(
+        &mut self,
+        row: WorksheetRow,
+        column: WorksheetCol,
+        chart: &Chart,
+    ) -> Result<(), XlsxError> {
+        self.insert_chart_opt(row, column, chart, &ImageOptions::default())
+    }
+
+    /// This function is like Worksheet.insert_chart() function except that it takes an `ImageOptions` struct to scale and position the chart:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_insert_chart_opt-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # for i in 0..5 {
+    /// #     worksheet.write_number(i, 0, (i*10).into(), None)?;
+    /// # }
+    /// # let mut chart = workbook.add_chart(ChartType::Column);
+    /// # chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    /// worksheet.insert_chart_opt(
+    ///     1, 3,
+    ///     &chart,
+    ///     &ImageOptions{
+    ///         x_offset: 10,
+    ///         y_offset: 5,
+    ///         x_scale: 2.,
+    ///         y_scale: 1.,
+    ///         ..Default::default()
+    ///     }
+    /// )?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// The scale is applied on top of the size set with `Chart.set_size()`.
+    pub fn insert_chart_opt(
+        &mut self,
+        row: WorksheetRow,
+        column: WorksheetCol,
+        chart: &Chart,
+        opt: &ImageOptions,
+    ) -> Result<(), XlsxError> {
+        chart.check_workbook(self._workbook)?;
+        let (x_scale, y_scale) = chart.size_scale();
+        let mut opt_struct = libxlsxwriter_sys::lxw_chart_options {
+            x_offset: opt.x_offset,
+            y_offset: opt.y_offset,
+            x_scale: opt.x_scale * x_scale,
+            y_scale: opt.y_scale * y_scale,
+            object_position: 0,
+            description: std::ptr::null_mut(),
+            decorative: 0,
+        };
+        unsafe {
+            let result = libxlsxwriter_sys::worksheet_insert_chart_opt(
+                self.worksheet,
+                row,
+                column,
+                chart.chart,
                 &mut opt_struct,
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -1510,15 +3430,25 @@ impl<'a> Worksheet<'a> {
         }
     }
 
-    pub fn insert_chart(
+    pub fn merge_range(
         &mut self,
-        row: WorksheetRow,
-        column: WorksheetCol,
-        chart: &Chart,
+        first_row: WorksheetRow,
+        first_col: WorksheetCol,
+        last_row: WorksheetRow,
+        last_col: WorksheetCol,
+        string: &str,
+        format: Option<&Format>,
     ) -> Result<(), XlsxError> {
         unsafe {
-            let result =
-                libxlsxwriter_sys::worksheet_insert_chart(self.worksheet, row, column, chart.chart);
+            let result = libxlsxwriter_sys::worksheet_merge_range(
+                self.worksheet,
+                first_row,
+                first_col,
+                last_row,
+                last_col,
+                to_c_string(string)?.as_ptr(),
+                format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
+            );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
                 Ok(())
             } else {
@@ -1527,31 +3457,64 @@ impl<'a> Worksheet<'a> {
         }
     }
 
-    pub fn merge_range(
+    /// Merge a range of cells and write a number to it. The range is merged with [`merge_range()`](#method.merge_range) and the number is then
+    /// written to the top-left cell with the same format, so the merged cell keeps its formatting:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_merge_range_number-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let format = workbook.add_format()
+    ///     .set_bold()
+    ///     .set_align(FormatAlignment::Center);
+    /// worksheet.merge_range_number(0, 0, 0, 3, 1234.5, Some(&format))?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn merge_range_number(
+        &mut self,
+        first_row: WorksheetRow,
+        first_col: WorksheetCol,
+        last_row: WorksheetRow,
+        last_col: WorksheetCol,
+        number: f64,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        // Check before merging so that a rejected number doesn't leave an empty merged range behind.
+        if !number.is_finite() {
+            return Err(
+                XlsxError::new(crate::error::NON_FINITE_NUMBER).at_cell(first_row, first_col)
+            );
+        }
+        self.merge_range(first_row, first_col, last_row, last_col, "", format)?;
+        self.write_number(first_row, first_col, number, format)
+    }
+
+    /// Merge a range of cells and write a date/time to it. See [`merge_range_number()`](#method.merge_range_number).
+    /// The format should have a number format for dates, otherwise the cell shows the serial number.
+    pub fn merge_range_datetime(
+        &mut self,
+        first_row: WorksheetRow,
+        first_col: WorksheetCol,
+        last_row: WorksheetRow,
+        last_col: WorksheetCol,
+        datetime: &DateTime,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        self.merge_range(first_row, first_col, last_row, last_col, "", format)?;
+        self.write_datetime(first_row, first_col, datetime, format)
+    }
+
+    /// Merge a range of cells without writing a value. The merged cell is blank but keeps the format.
+    pub fn merge_range_blank(
         &mut self,
         first_row: WorksheetRow,
         first_col: WorksheetCol,
         last_row: WorksheetRow,
         last_col: WorksheetCol,
-        string: &str,
         format: Option<&Format>,
     ) -> Result<(), XlsxError> {
-        unsafe {
-            let result = libxlsxwriter_sys::worksheet_merge_range(
-                self.worksheet,
-                first_row,
-                first_col,
-                last_row,
-                last_col,
-                CString::new(string).unwrap().as_c_str().as_ptr(),
-                format.map(|x| x.format).unwrap_or(std::ptr::null_mut()),
-            );
-            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
-                Ok(())
-            } else {
-                Err(XlsxError::new(result))
-            }
-        }
+        self.merge_range(first_row, first_col, last_row, last_col, "", format)
     }
 
     /// This function allows an autofilter to be added to a worksheet.
@@ -1581,6 +3544,85 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// This function can be used to filter columns in a autofilter range based on simple conditions.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_filter_column-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # worksheet.write_string(0, 0, "Region", None)?;
+    /// # worksheet.write_string(0, 1, "Sales", None)?;
+    /// worksheet.autofilter(0, 0, 10, 1)?;
+    /// worksheet.filter_column(1, &FilterRule::new(FilterCriteria::GreaterThan, 1000.))?;
+    /// worksheet.filter_column(
+    ///     0,
+    ///     &FilterRule::new(FilterCriteria::EqualTo, "East").or(FilterCriteria::EqualTo, "West"),
+    /// )?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// The column must be inside the range set with [`Worksheet::autofilter()`].
+    ///
+    /// The filter conditions are only written to the file. libxlsxwriter doesn't hide the rows that don't match, so the caller must hide them with [`Worksheet::set_row_opt()`], otherwise Excel displays all rows until the filter is reapplied.
+    pub fn filter_column(&mut self, col: WorksheetCol, rule: &FilterRule) -> Result<(), XlsxError> {
+        let mut value_string1 = None;
+        let mut rule1 = filter_rule_to_c_struct(rule.criteria, &rule.value, &mut value_string1)?;
+        unsafe {
+            let result = if let Some((operator, criteria, value)) = &rule.second {
+                let mut value_string2 = None;
+                let mut rule2 = filter_rule_to_c_struct(*criteria, value, &mut value_string2)?;
+                libxlsxwriter_sys::worksheet_filter_column2(
+                    self.worksheet,
+                    col,
+                    &mut rule1,
+                    &mut rule2,
+                    operator.value(),
+                )
+            } else {
+                libxlsxwriter_sys::worksheet_filter_column(self.worksheet, col, &mut rule1)
+            };
+            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                Ok(())
+            } else {
+                Err(XlsxError::new(result))
+            }
+        }
+    }
+
+    /// This function can be used to filter columns in a autofilter range to show only the cells matching one of the given strings.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_filter_list-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// # worksheet.write_string(0, 0, "Region", None)?;
+    /// worksheet.autofilter(0, 0, 10, 0)?;
+    /// worksheet.filter_list(0, &["North", "South", "East"])?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// As with [`Worksheet::filter_column()`], rows that don't match are not hidden automatically.
+    pub fn filter_list(&mut self, col: WorksheetCol, list: &[&str]) -> Result<(), XlsxError> {
+        let c_list: Vec<CString> = list
+            .iter()
+            .map(|x| to_c_string(x))
+            .collect::<Result<_, _>>()?;
+        let mut ptr_list: Vec<*const c_char> = c_list.iter().map(|x| x.as_ptr()).collect();
+        ptr_list.push(std::ptr::null());
+        unsafe {
+            let result = libxlsxwriter_sys::worksheet_filter_list(
+                self.worksheet,
+                col,
+                ptr_list.as_mut_ptr(),
+            );
+            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                Ok(())
+            } else {
+                Err(XlsxError::new(result))
+            }
+        }
+    }
+
     /// This function is used to construct an Excel data validation or to limit the user input to a dropdown list of values
     pub fn data_validation_cell(
         &mut self,
@@ -1588,8 +3630,8 @@ impl<'a> Worksheet<'a> {
         col: WorksheetCol,
         validation: &DataValidation,
     ) -> Result<(), XlsxError> {
+        let mut validation = validation.to_c_struct()?;
         unsafe {
-            let mut validation = validation.to_c_struct();
             let result = libxlsxwriter_sys::worksheet_data_validation_cell(
                 self.worksheet,
                 row,
@@ -1612,6 +3654,7 @@ impl<'a> Worksheet<'a> {
         last_col: WorksheetCol,
         validation: &DataValidation,
     ) -> Result<(), XlsxError> {
+        let mut validation = validation.to_c_struct()?;
         unsafe {
             let result = libxlsxwriter_sys::worksheet_data_validation_range(
                 self.worksheet,
@@ -1619,7 +3662,7 @@ impl<'a> Worksheet<'a> {
                 first_col,
                 last_row,
                 last_col,
-                &mut validation.to_c_struct().data_validation,
+                &mut validation.data_validation,
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
                 Ok(())
@@ -1661,31 +3704,60 @@ impl<'a> Worksheet<'a> {
         last_col: WorksheetCol,
         options: Option<TableOptions<'a>>,
     ) -> Result<(), XlsxError> {
-        eprintln!(
-            "col: {} {} {:?}",
-            first_col,
-            last_col,
-            options
-                .as_ref()
-                .map(|x| x.columns.as_ref().map(|y| y.len()))
-        );
         if options
             .as_ref()
             .map(|x| {
                 x.columns
                     .as_ref()
-                    .map(|y| y.len() != (last_col - first_col + 1).into())
+                    .map(|y| y.len() != usize::from(last_col - first_col + 1))
                     .unwrap_or(false)
             })
             .unwrap_or(false)
         {
-            return Err(XlsxError {
-                error: crate::error::NUMBER_OF_COLUMNS_IS_NOT_MATCHED,
-            });
+            return Err(XlsxError::new(
+                crate::error::NUMBER_OF_COLUMNS_IS_NOT_MATCHED,
+            ));
         }
 
+        if let Some(columns) = options.as_ref().and_then(|x| x.columns.as_ref()) {
+            if columns.iter().any(|x| {
+                x.formula
+                    .as_deref()
+                    .map(|y| {
+                        y.trim_start_matches(|c: char| c == '=' || c.is_whitespace())
+                            .is_empty()
+                    })
+                    .unwrap_or(false)
+            }) {
+                return Err(XlsxError::new(crate::error::EMPTY_FORMULA));
+            }
+            // A column's total row cell holds either a label or a function, not both.
+            if let Some(i) = columns.iter().position(|x| {
+                x.total_string.is_some() && x.total_function != TableTotalFunction::None
+            }) {
+                let err = XlsxError::new(crate::error::TABLE_TOTAL_CONFLICT);
+                let col = WorksheetCol::try_from(i)
+                    .ok()
+                    .and_then(|i| first_col.checked_add(i));
+                return Err(match col {
+                    Some(col) => err.at_cell(last_row, col),
+                    None => err,
+                });
+            }
+        }
+
+        if let Some(options) = options.as_ref() {
+            if let Some(range) = options.style_type.style_number_range() {
+                if !range.contains(&options.style_type_number) {
+                    return Err(XlsxError::new(
+                        crate::error::TABLE_STYLE_NUMBER_OUT_OF_RANGE,
+                    ));
+                }
+            }
+        }
+
+        let mut options = options.map(|x| x.to_lxw_table_options()).transpose()?;
         unsafe {
-            let mut options = options.map(|x| x.into_lxw_table_options());
             let result = libxlsxwriter_sys::worksheet_add_table(
                 self.worksheet,
                 first_row,
@@ -1694,7 +3766,7 @@ impl<'a> Worksheet<'a> {
                 last_col,
                 options
                     .as_mut()
-                    .map(|x| &mut x.1 as *mut libxlsxwriter_sys::lxw_table_options)
+                    .map(|x| &mut x.options as *mut libxlsxwriter_sys::lxw_table_options)
                     .unwrap_or(std::ptr::null_mut()),
             );
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -1705,6 +3777,80 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// Add a table with the given style and default options. This is a shortcut for `add_table()` with [`TableOptions::styled()`].
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_add_table_simple-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.write_string(0, 0, "Region", None)?;
+    /// worksheet.write_string(1, 0, "North", None)?;
+    /// worksheet.add_table_simple(0, 0, 1, 0, TableStyleType::Light, 11)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn add_table_simple(
+        &mut self,
+        first_row: WorksheetRow,
+        first_col: WorksheetCol,
+        last_row: WorksheetRow,
+        last_col: WorksheetCol,
+        style_type: TableStyleType,
+        style_number: u8,
+    ) -> Result<(), XlsxError> {
+        self.add_table(
+            first_row,
+            first_col,
+            last_row,
+            last_col,
+            Some(TableOptions::styled(style_type, style_number)),
+        )
+    }
+
+    /// Return the zero based position of the worksheet tab in the workbook. Sheets are ordered in the order they were added, including chartsheets.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_index-1.xlsx");
+    /// let worksheet1 = workbook.add_worksheet(None)?;
+    /// let worksheet2 = workbook.add_worksheet(None)?;
+    /// assert_eq!(worksheet1.index(), 0);
+    /// assert_eq!(worksheet2.index(), 1);
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// libxlsxwriter doesn't support reordering sheets, so the index can't be changed after the worksheet is added.
+    ///
+    /// # Panics
+    /// Panics if the workbook has more than 65536 sheets and this worksheet was added after the 65536th. libxlsxwriter stores
+    /// the index as a `u32`, but such a workbook is far beyond what Excel can open.
+    pub fn index(&self) -> u16 {
+        let index = unsafe { (*self.worksheet).index };
+        u16::try_from(index).expect("worksheet index exceeds u16::MAX")
+    }
+
+    /// Return the name of the worksheet. Worksheets added with `add_worksheet(None)` get the default names `Sheet1`, `Sheet2` and so on,
+    /// which is useful for building `internal:` links:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_name-1.xlsx");
+    /// let worksheet1 = workbook.add_worksheet(None)?;
+    /// let mut worksheet2 = workbook.add_worksheet(Some("Summary"))?;
+    /// assert_eq!(worksheet1.name(), "Sheet1");
+    /// assert_eq!(worksheet2.name(), "Summary");
+    /// worksheet2.write_url(0, 0, &format!("internal:{}!A1", worksheet1.name()), None)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn name(&self) -> String {
+        unsafe {
+            std::ffi::CStr::from_ptr((*self.worksheet).name)
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+
     pub fn activate(&mut self) {
         unsafe {
             libxlsxwriter_sys::worksheet_activate(self.worksheet);
@@ -1735,27 +3881,127 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// This function is the same as `freeze_panes()`, but also sets the first visible row and column of the scrolling pane.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_freeze_panes_opt-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// // Freeze the header row and scroll the body down to row 51.
+    /// worksheet.freeze_panes_opt(1, 0, 50, 0, FreezePaneType::Frozen);
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// `top_row` and `left_col` are zero indexed like `row` and `col`.
+    pub fn freeze_panes_opt(
+        &mut self,
+        row: WorksheetRow,
+        col: WorksheetCol,
+        top_row: WorksheetRow,
+        left_col: WorksheetCol,
+        pane_type: FreezePaneType,
+    ) {
+        unsafe {
+            libxlsxwriter_sys::worksheet_freeze_panes_opt(
+                self.worksheet,
+                row,
+                col,
+                top_row,
+                left_col,
+                pane_type.value(),
+            );
+        }
+    }
+
     pub fn split_panes(&mut self, vertical: f64, horizontal: f64) {
         unsafe {
             libxlsxwriter_sys::worksheet_split_panes(self.worksheet, vertical, horizontal);
         }
     }
 
+    /// Select a range of cells when the worksheet is opened. The first cell of the range is the active cell, use
+    /// `set_selection_active()` to make another corner of the range active.
+    ///
+    /// An error is returned if `first_row` or `first_col` is after `last_row` or `last_col`, or if the selection was already set.
+    /// Excel supports several discontiguous selected ranges, but libxlsxwriter only supports one.
     pub fn set_selection(
         &mut self,
         first_row: WorksheetRow,
         first_col: WorksheetCol,
         last_row: WorksheetRow,
         last_col: WorksheetCol,
-    ) {
+    ) -> Result<(), XlsxError> {
+        check_range(first_row, first_col, last_row, last_col)?;
+        unsafe {
+            let result = libxlsxwriter_sys::worksheet_set_selection(
+                self.worksheet,
+                first_row,
+                first_col,
+                last_row,
+                last_col,
+            );
+            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                Ok(())
+            } else {
+                Err(XlsxError::new(result))
+            }
+        }
+    }
+
+    /// Select a range of cells with `(active_row, active_col)` as the active cell, e.g. to pre-select the input cell of a form:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_set_selection_active-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// // Select B2:D10 with D10 as the active cell.
+    /// worksheet.set_selection_active(1, 1, 9, 3, 9, 3)?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// libxlsxwriter can only make a corner of the range active, any other active cell returns an error.
+    pub fn set_selection_active(
+        &mut self,
+        first_row: WorksheetRow,
+        first_col: WorksheetCol,
+        last_row: WorksheetRow,
+        last_col: WorksheetCol,
+        active_row: WorksheetRow,
+        active_col: WorksheetCol,
+    ) -> Result<(), XlsxError> {
+        check_range(first_row, first_col, last_row, last_col)?;
+        // libxlsxwriter makes the first cell it is given active and then orders the range, so the active corner goes first.
+        let (first_row, last_row) = match active_row {
+            x if x == first_row => (first_row, last_row),
+            x if x == last_row => (last_row, first_row),
+            _ => {
+                return Err(XlsxError::new(
+                    libxlsxwriter_sys::lxw_error_LXW_ERROR_PARAMETER_VALIDATION,
+                ))
+            }
+        };
+        let (first_col, last_col) = match active_col {
+            x if x == first_col => (first_col, last_col),
+            x if x == last_col => (last_col, first_col),
+            _ => {
+                return Err(XlsxError::new(
+                    libxlsxwriter_sys::lxw_error_LXW_ERROR_PARAMETER_VALIDATION,
+                ))
+            }
+        };
         unsafe {
-            libxlsxwriter_sys::worksheet_set_selection(
+            let result = libxlsxwriter_sys::worksheet_set_selection(
                 self.worksheet,
                 first_row,
                 first_col,
                 last_row,
                 last_col,
             );
+            if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
+                Ok(())
+            } else {
+                Err(XlsxError::new(result))
+            }
         }
     }
 
@@ -1779,7 +4025,48 @@ impl<'a> Worksheet<'a> {
 
     pub fn set_paper(&mut self, paper: PaperType) {
         unsafe {
-            libxlsxwriter_sys::worksheet_set_paper(self.worksheet, paper.value());
+            libxlsxwriter_sys::worksheet_set_paper(self.worksheet, paper.code());
+        }
+    }
+
+    /// This function is used to set the margins of the worksheet when it is printed. The units are in inches. Specifying `-1.0` for any parameter will give the default Excel value.
+    pub fn set_margins(&mut self, left: f64, right: f64, top: f64, bottom: f64) {
+        unsafe {
+            libxlsxwriter_sys::worksheet_set_margins(self.worksheet, left, right, top, bottom);
+        }
+    }
+
+    /// This function is the same as `set_margins()` but takes the margins in centimeters. A negative value gives the default Excel value.
+    pub fn set_margins_cm(&mut self, left: f64, right: f64, top: f64, bottom: f64) {
+        let to_inches = |x: f64| if x < 0. { x } else { x / 2.54 };
+        self.set_margins(
+            to_inches(left),
+            to_inches(right),
+            to_inches(top),
+            to_inches(bottom),
+        );
+    }
+
+    /// Return the `(left, right, top, bottom)` print margins of the worksheet in inches.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_margins-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.set_margins(0.5, 0.5, 1.0, 1.0);
+    /// assert_eq!(worksheet.margins(), (0.5, 0.5, 1.0, 1.0));
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn margins(&self) -> (f64, f64, f64, f64) {
+        unsafe {
+            let worksheet = &*self.worksheet;
+            (
+                worksheet.margin_left,
+                worksheet.margin_right,
+                worksheet.margin_top,
+                worksheet.margin_bottom,
+            )
         }
     }
 
@@ -1787,7 +4074,7 @@ impl<'a> Worksheet<'a> {
         unsafe {
             let result = libxlsxwriter_sys::worksheet_set_header(
                 self.worksheet,
-                CString::new(header).unwrap().as_c_str().as_ptr(),
+                to_c_string(header)?.as_ptr(),
             );
 
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -1802,7 +4089,7 @@ impl<'a> Worksheet<'a> {
         unsafe {
             let result = libxlsxwriter_sys::worksheet_set_footer(
                 self.worksheet,
-                CString::new(footer).unwrap().as_c_str().as_ptr(),
+                to_c_string(footer)?.as_ptr(),
             );
 
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -1821,7 +4108,7 @@ impl<'a> Worksheet<'a> {
         unsafe {
             let result = libxlsxwriter_sys::worksheet_set_header_opt(
                 self.worksheet,
-                CString::new(header).unwrap().as_c_str().as_ptr(),
+                to_c_string(header)?.as_ptr(),
                 &mut options.into(),
             );
 
@@ -1841,7 +4128,7 @@ impl<'a> Worksheet<'a> {
         unsafe {
             let result = libxlsxwriter_sys::worksheet_set_footer_opt(
                 self.worksheet,
-                CString::new(footer).unwrap().as_c_str().as_ptr(),
+                to_c_string(footer)?.as_ptr(),
                 &mut options.into(),
             );
 
@@ -1893,18 +4180,51 @@ impl<'a> Worksheet<'a> {
         }
     }
 
-    pub fn set_zoom(&mut self, scale: u16) {
+    /// Set the worksheet zoom factor in the range `10 <= scale <= 400`. A scale outside of this range returns an error and leaves the current zoom unchanged.
+    pub fn set_zoom(&mut self, scale: u16) -> Result<(), XlsxError> {
+        check_scale(scale)?;
         unsafe {
             libxlsxwriter_sys::worksheet_set_zoom(self.worksheet, scale);
         }
+        Ok(())
     }
 
+    /// Prefer [`Worksheet::show_gridlines()`], which sets the screen and print gridlines independently.
+    ///
+    /// The `GridLines` states map to `show_gridlines()` as follows:
+    /// * `HideAllGridLines`: `show_gridlines(false, false)`
+    /// * `ShowScreenGridLines`: `show_gridlines(true, false)`
+    /// * `ShowPrintGridLines`: `show_gridlines(false, true)`
+    /// * `ShowAllGridLines`: `show_gridlines(true, true)`
     pub fn gridlines(&mut self, option: GridLines) {
         unsafe {
             libxlsxwriter_sys::worksheet_gridlines(self.worksheet, option.value());
         }
     }
 
+    /// This function is used to turn on or off the display of the gridlines on screen and when the worksheet is printed.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_show_gridlines-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// // Hide the gridlines on screen but print them.
+    /// worksheet.show_gridlines(false, true);
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// By default Excel displays gridlines on screen but doesn't print them.
+    pub fn show_gridlines(&mut self, screen: bool, print: bool) {
+        // libxlsxwriter only turns gridlines on, except for HideAllGridLines which turns both off, so reset them first.
+        self.gridlines(GridLines::HideAllGridLines);
+        match (screen, print) {
+            (false, false) => {}
+            (true, false) => self.gridlines(GridLines::ShowScreenGridLines),
+            (false, true) => self.gridlines(GridLines::ShowPrintGridLines),
+            (true, true) => self.gridlines(GridLines::ShowAllGridLines),
+        }
+    }
+
     pub fn center_horizontally(&mut self) {
         unsafe {
             libxlsxwriter_sys::worksheet_center_horizontally(self.worksheet);
@@ -1923,6 +4243,36 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// Apply several print settings in one call, e.g. from a deserialized configuration.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_set_print_options-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.set_print_options(&PrintOptions {
+    ///     center_horizontally: true,
+    ///     gridlines: Some(GridLines::ShowPrintGridLines),
+    ///     ..Default::default()
+    /// });
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// This is the same as calling `center_horizontally()`, `center_vertically()`, `print_row_col_headers()` and `gridlines()` for each enabled setting.
+    pub fn set_print_options(&mut self, options: &PrintOptions) {
+        if options.center_horizontally {
+            self.center_horizontally();
+        }
+        if options.center_vertically {
+            self.center_vertically();
+        }
+        if options.print_row_col_headers {
+            self.print_row_col_headers();
+        }
+        if let Some(gridlines) = options.gridlines {
+            self.gridlines(gridlines);
+        }
+    }
+
     pub fn repeat_rows(
         &mut self,
         first_row: WorksheetRow,
@@ -1984,16 +4334,41 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// Set up the worksheet to be printed on a single page with the given orientation and paper size. The narrow Excel margins (0.25 inch left and right, 0.75 inch top and bottom) are used and the content is centered horizontally.
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_fit_on_one_page-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// worksheet.fit_on_one_page(Orientation::Landscape, PaperType::A4);
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// This is a shortcut for calling `set_landscape()`/`set_portrait()`, `set_paper()`, `fit_to_pages(1, 1)`, `set_margins()` and `center_horizontally()`.
+    pub fn fit_on_one_page(&mut self, orientation: Orientation, paper: PaperType) {
+        match orientation {
+            Orientation::Portrait => self.set_portrait(),
+            Orientation::Landscape => self.set_landscape(),
+        }
+        self.set_paper(paper);
+        self.fit_to_pages(1, 1);
+        self.set_margins(0.25, 0.25, 0.75, 0.75);
+        self.center_horizontally();
+    }
+
     pub fn set_start_page(&mut self, start_page: u16) {
         unsafe {
             libxlsxwriter_sys::worksheet_set_start_page(self.worksheet, start_page);
         }
     }
 
-    pub fn set_print_scale(&mut self, scale: u16) {
+    /// Set the scale factor of the printed page in the range `10 <= scale <= 400`. A scale outside of this range returns an error.
+    pub fn set_print_scale(&mut self, scale: u16) -> Result<(), XlsxError> {
+        check_scale(scale)?;
         unsafe {
             libxlsxwriter_sys::worksheet_set_print_scale(self.worksheet, scale);
         }
+        Ok(())
     }
 
     pub fn set_right_to_left(&mut self) {
@@ -2008,20 +4383,87 @@ impl<'a> Worksheet<'a> {
         }
     }
 
+    /// Set the color of the worksheet tab. Predefined and custom RGB colors are both supported:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_set_tab_color-1.xlsx");
+    /// let mut worksheet1 = workbook.add_worksheet(None)?;
+    /// let mut worksheet2 = workbook.add_worksheet(None)?;
+    /// worksheet1.set_tab_color(FormatColor::Red);
+    /// worksheet2.set_tab_color(FormatColor::rgb(0x1A2B3C));
+    /// # workbook.close()
+    /// # }
+    /// ```
     pub fn set_tab_color(&mut self, color: FormatColor) {
         unsafe {
             libxlsxwriter_sys::worksheet_set_tab_color(self.worksheet, color.value());
         }
     }
 
-    pub fn protect(&mut self, password: &str, protection: &Protection) {
+    pub fn protect(&mut self, password: &str, protection: &Protection) -> Result<(), XlsxError> {
+        let password = to_c_string(password)?;
         unsafe {
             libxlsxwriter_sys::worksheet_protect(
                 self.worksheet,
-                CString::new(password).unwrap().as_c_str().as_ptr(),
+                password.as_ptr(),
                 &mut protection.into(),
             );
         }
+        Ok(())
+    }
+
+    /// Protect the worksheet without a password, using the default [`Protection`] settings. Cells are locked, but the sheet can be
+    /// unprotected in Excel without a password:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_protect_default-1.xlsx");
+    /// let mut worksheet1 = workbook.add_worksheet(None)?;
+    /// let mut worksheet2 = workbook.add_worksheet(None)?;
+    /// worksheet1.protect_default();
+    /// worksheet2.protect_with_password("secret")?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    /// Use [`Worksheet::protect()`] to allow some actions such as formatting or sorting on the protected sheet.
+    pub fn protect_default(&mut self) {
+        unsafe {
+            libxlsxwriter_sys::worksheet_protect(
+                self.worksheet,
+                std::ptr::null(),
+                std::ptr::null_mut(),
+            );
+        }
+    }
+
+    /// Protect the worksheet with a password, using the default [`Protection`] settings. See [`Worksheet::protect_default()`].
+    ///
+    /// Only a short hash of the password is stored in the file. It stops casual edits, but it is not encryption.
+    ///
+    /// To leave input cells editable, as in a form, write them with an unlocked format:
+    /// ```rust
+    /// # use xlsxwriter::*;
+    /// # fn main() -> Result<(), XlsxError> {
+    /// # let workbook = Workbook::new("test-worksheet_protect_with_password-1.xlsx");
+    /// # let mut worksheet = workbook.add_worksheet(None)?;
+    /// let input = workbook.add_format().set_unlocked();
+    /// worksheet.write_string(0, 0, "Name", None)?;
+    /// worksheet.write_blank(0, 1, Some(&input))?;
+    /// worksheet.protect_with_password("secret")?;
+    /// # workbook.close()
+    /// # }
+    /// ```
+    pub fn protect_with_password(&mut self, password: &str) -> Result<(), XlsxError> {
+        let password = to_c_string(password)?;
+        unsafe {
+            libxlsxwriter_sys::worksheet_protect(
+                self.worksheet,
+                password.as_ptr(),
+                std::ptr::null_mut(),
+            );
+        }
+        Ok(())
     }
 
     pub fn outline_settings(
@@ -2056,7 +4498,7 @@ impl<'a> Worksheet<'a> {
         unsafe {
             let result = libxlsxwriter_sys::worksheet_set_vba_name(
                 self.worksheet,
-                CString::new(name).unwrap().as_c_str().as_ptr(),
+                to_c_string(name)?.as_ptr(),
             );
 
             if result == libxlsxwriter_sys::lxw_error_LXW_NO_ERROR {
@@ -2073,6 +4515,7 @@ impl<'a> Worksheet<'a> {
         col: WorksheetCol,
         format: &mut ConditionalFormat,
     ) -> Result<(), XlsxError> {
+        format.update_c_strings()?;
         unsafe {
             let result = libxlsxwriter_sys::worksheet_conditional_format_cell(
                 self.worksheet,
@@ -2096,6 +4539,7 @@ impl<'a> Worksheet<'a> {
         last_col: WorksheetCol,
         format: &mut ConditionalFormat,
     ) -> Result<(), XlsxError> {
+        format.update_c_strings()?;
         unsafe {
             let result = libxlsxwriter_sys::worksheet_conditional_format_range(
                 self.worksheet,
@@ -0,0 +1,6 @@
+/// Misuses of the API that the borrow checker must reject, e.g. using a chart after its workbook was closed.
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use xlsxwriter::*;
+
+fn main() -> Result<(), XlsxError> {
+    let workbook = Workbook::new("test-chart-after-close.xlsx");
+    let mut chart = workbook.add_chart(ChartType::Column);
+    workbook.close()?;
+    chart.add_series(None, Some("=Sheet1!$A$1:$A$5"))?;
+    Ok(())
+}